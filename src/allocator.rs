@@ -0,0 +1,156 @@
+extern crate alloc;
+
+use core::{alloc::Layout, fmt, ptr::NonNull};
+
+/// Memory allocator used by `Vector` and `Str` for their spilled storage.
+///
+/// This mirrors the unstable `core::alloc::Allocator` closely enough that an
+/// implementation can usually forward to it, but works on stable `no_std`.
+/// Neither type ever requests a zero-sized layout.
+///
+/// # Safety
+///
+/// A block returned by `allocate` or `grow` must stay valid until it is passed
+/// to `deallocate` or `grow` on the same allocator (or a clone of it), and must
+/// fit the layout it was requested with.
+pub unsafe trait Allocator {
+    /// Allocates a block fitting `layout`.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// Releases a block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator with `layout`.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Grows a block to `new_layout`, preserving its contents.
+    ///
+    /// On failure the original block is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator with
+    /// `old_layout`, and `new_layout.size()` must be at least `old_layout.size()`.
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    /// Shrinks a block to `new_layout`, preserving the contents that still fit.
+    ///
+    /// On failure the original block is left untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator with
+    /// `old_layout`, and `new_layout.size()` must be at most `old_layout.size()`.
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), new_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new_ptr)
+    }
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        (**self).shrink(ptr, old_layout, new_layout)
+    }
+}
+
+/// The global memory allocator, as used by `alloc::vec::Vec`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        NonNull::new(unsafe { alloc::alloc::alloc(layout) }).ok_or(AllocError)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        alloc::alloc::dealloc(ptr.as_ptr(), layout)
+    }
+
+    #[inline]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        debug_assert_eq!(old_layout.align(), new_layout.align());
+        NonNull::new(alloc::alloc::realloc(
+            ptr.as_ptr(),
+            old_layout,
+            new_layout.size(),
+        ))
+        .ok_or(AllocError)
+    }
+
+    #[inline]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        debug_assert_eq!(old_layout.align(), new_layout.align());
+        NonNull::new(alloc::alloc::realloc(
+            ptr.as_ptr(),
+            old_layout,
+            new_layout.size(),
+        ))
+        .ok_or(AllocError)
+    }
+}
+
+/// The allocator could not satisfy a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
@@ -0,0 +1,120 @@
+use core::{alloc::Layout, fmt, str::Utf8Error};
+
+use crate::{
+    allocator::{Allocator, Global},
+    growth::{Doubling, GrowthPolicy},
+    Vector, INLINE_SIZE,
+};
+
+/// Error returned by the fallible allocation methods of `Vector` and `Str`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+/// Details of a [`TryReserveError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TryReserveErrorKind {
+    /// The requested capacity exceeds the maximum a `Vector` or `Str` can hold.
+    CapacityOverflow,
+    /// The allocator could not provide a block for `layout`.
+    AllocError { layout: Layout },
+}
+
+impl TryReserveError {
+    #[inline]
+    pub fn kind(&self) -> TryReserveErrorKind {
+        self.kind.clone()
+    }
+}
+
+impl From<TryReserveErrorKind> for TryReserveError {
+    #[inline]
+    fn from(kind: TryReserveErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")?;
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                f.write_str(" because the computed capacity exceeded the maximum")
+            }
+            TryReserveErrorKind::AllocError { .. } => {
+                f.write_str(" because the memory allocator returned an error")
+            }
+        }
+    }
+}
+
+/// Error returned by `Str::from_utf8` when the bytes are not valid UTF-8.
+///
+/// Owns the original buffer, which can be taken back with [`FromUtf8Error::into_bytes`].
+pub struct FromUtf8Error<
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    bytes: Vector<u8, N, A, G>,
+    error: Utf8Error,
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> FromUtf8Error<N, A, G> {
+    #[inline]
+    pub(crate) fn new(bytes: Vector<u8, N, A, G>, error: Utf8Error) -> Self {
+        Self { bytes, error }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes that failed to convert, without copying them.
+    #[inline]
+    pub fn into_bytes(self) -> Vector<u8, N, A, G> {
+        self.bytes
+    }
+
+    /// Returns where and why the conversion failed.
+    #[inline]
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+impl<const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Clone
+    for FromUtf8Error<N, A, G>
+{
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            error: self.error,
+        }
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> PartialEq for FromUtf8Error<N, A, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.error == other.error
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Eq for FromUtf8Error<N, A, G> {}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> fmt::Debug for FromUtf8Error<N, A, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("bytes", &self.bytes)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> fmt::Display for FromUtf8Error<N, A, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
@@ -0,0 +1,86 @@
+/// Decides how much spilled storage to allocate when a `Vector` runs out of room.
+///
+/// Used by every amortized growth path (`push`, `insert`, `extend`,
+/// `extend_from_slice` and `reserve`); the `_exact` methods bypass it.
+pub trait GrowthPolicy {
+    /// Returns the capacity to grow to from `capacity` so that at least
+    /// `required` elements fit.
+    ///
+    /// `capacity` is the inline capacity when the vector has not spilled yet.
+    /// Results smaller than `required` are rounded up to it.
+    fn new_capacity(&self, capacity: usize, required: usize) -> usize;
+}
+
+impl<P: GrowthPolicy + ?Sized> GrowthPolicy for &P {
+    #[inline]
+    fn new_capacity(&self, capacity: usize, required: usize) -> usize {
+        (**self).new_capacity(capacity, required)
+    }
+}
+
+/// Doubles the capacity, the default policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+    #[inline]
+    fn new_capacity(&self, capacity: usize, required: usize) -> usize {
+        capacity.saturating_mul(2).max(required)
+    }
+}
+
+/// Grows the capacity by half, trading more reallocations for less slack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OneAndAHalf;
+
+impl GrowthPolicy for OneAndAHalf {
+    #[inline]
+    fn new_capacity(&self, capacity: usize, required: usize) -> usize {
+        capacity.saturating_add(capacity / 2).max(required)
+    }
+}
+
+/// Rounds the capacity up to the next multiple of the chunk size.
+///
+/// A chunk size of zero is treated as one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedChunk(pub usize);
+
+impl GrowthPolicy for FixedChunk {
+    #[inline]
+    fn new_capacity(&self, _capacity: usize, required: usize) -> usize {
+        let chunk = self.0.max(1);
+        required
+            .div_ceil(chunk)
+            .checked_mul(chunk)
+            .unwrap_or(required)
+    }
+}
+
+/// Allocates exactly what is required, never leaving slack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Exact;
+
+impl GrowthPolicy for Exact {
+    #[inline]
+    fn new_capacity(&self, _capacity: usize, required: usize) -> usize {
+        required
+    }
+}
+
+/// Never allocates fewer than `min` elements, deferring to `then` otherwise.
+///
+/// Avoids the 1, 2, 4, ... reallocations of a vector that starts out empty
+/// on the heap, or a tiny first spill for small inline capacities.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinimumFirst<P = Doubling> {
+    pub min: usize,
+    pub then: P,
+}
+
+impl<P: GrowthPolicy> GrowthPolicy for MinimumFirst<P> {
+    #[inline]
+    fn new_capacity(&self, capacity: usize, required: usize) -> usize {
+        self.then.new_capacity(capacity, required).max(self.min)
+    }
+}
@@ -1,13 +1,21 @@
 #![no_std]
 
+mod allocator;
+mod error;
+mod growth;
 mod repr;
-mod str;
-mod vec;
+pub mod str;
+pub mod vec;
 
+/// Default number of inline bytes, sized so `Vector` and `Str` are as large as `Vec` and `String`.
 #[cfg(target_pointer_width = "64")]
-const INLINE_SIZE: usize = 23;
+pub const INLINE_SIZE: usize = 23;
+/// Default number of inline bytes, sized so `Vector` and `Str` are as large as `Vec` and `String`.
 #[cfg(target_pointer_width = "32")]
-const INLINE_SIZE: usize = 11;
+pub const INLINE_SIZE: usize = 11;
 
-pub use crate::str::Str;
+pub use crate::allocator::{AllocError, Allocator, Global};
+pub use crate::error::{FromUtf8Error, TryReserveError, TryReserveErrorKind};
+pub use crate::growth::{Doubling, Exact, FixedChunk, GrowthPolicy, MinimumFirst, OneAndAHalf};
+pub use crate::str::{Str, ToStr};
 pub use crate::vec::Vector;
@@ -1,193 +1,254 @@
 extern crate alloc;
-use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
+use alloc::alloc::handle_alloc_error;
 
-use crate::INLINE_SIZE;
+use crate::allocator::{Allocator, Global};
+use crate::error::{TryReserveError, TryReserveErrorKind};
+use crate::growth::{Doubling, GrowthPolicy};
 use core::{
     alloc::Layout,
-    marker::PhantomData,
     mem::{self, ManuallyDrop, MaybeUninit},
+    num::NonZeroU8,
     ptr::{self, NonNull},
 };
 
+const WORD_SIZE: usize = mem::size_of::<usize>();
+
+/// Storage shared by `Vector` and `Str`.
+///
+/// `N` is the number of bytes available for inline storage after the
+/// discriminant byte. Inline elements start at the first offset aligned for
+/// `T`, so over-aligned types lose the bytes in front of that offset.
+///
+/// The discriminant is a real field rather than part of a union, so the zero
+/// value it never takes is available as a niche for `Option<Repr>`.
+///
+/// Heap buffers are obtained from and returned to `alloc`, and sized by `growth`.
 #[repr(C)]
-pub union Repr<T> {
-    pub(crate) heap: ManuallyDrop<Heap<T>>,
-    pub(crate) inline: ManuallyDrop<Inline<T>>,
+pub struct Repr<T, const N: usize, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    _align: [usize; 0],
+    _align_elem: [T; 0],
+    disc: Discriminant,
+    data: Data<T, N>,
+    alloc: A,
+    growth: G,
 }
 
 #[repr(C)]
-pub struct Inline<T> {
-    pub(crate) disc: Discriminant,
-    pub(crate) data: [u8; INLINE_SIZE],
-    pub(crate) _phantom: PhantomData<T>,
+union Data<T, const N: usize> {
+    heap: ManuallyDrop<Heap<T>>,
+    inline: [MaybeUninit<u8>; N],
 }
 
-#[repr(C)]
-pub struct Heap<T> {
-    pub(crate) capacity: usize,
+/// Heap representation, following the discriminant byte.
+///
+/// The capacity is stored as a big-endian word with [`Heap::MARKER`] set,
+/// whose most significant byte is the discriminant itself; `capacity` holds
+/// the remaining bytes. The struct is packed so that it can start right after
+/// the discriminant, but since `Repr` is word aligned `len` and `ptr` still
+/// land on aligned addresses.
+#[repr(C, packed)]
+pub(crate) struct Heap<T> {
+    capacity: [u8; WORD_SIZE - 1],
     pub(crate) len: usize,
     pub(crate) ptr: NonNull<T>,
 }
 
-impl<T> Drop for Repr<T> {
+impl<T> Heap<T> {
+    /// Largest capacity that can be stored without touching [`Heap::MARKER`].
+    pub(crate) const MAX_CAPACITY: usize = isize::MAX as usize;
+
+    /// Set in every stored capacity word so its top byte is a heap discriminant.
+    const MARKER: usize = !Self::MAX_CAPACITY;
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for Repr<T, N, A, G> {
     fn drop(&mut self) {
-        match self.is_inline() {
-            true => unsafe { ManuallyDrop::drop(&mut self.inline) },
-            false => {
-                unsafe { ManuallyDrop::drop(&mut self.heap) };
+        if !self.is_inline() {
+            let capacity = self.capacity();
 
-                let self_heap = self.get_heap_mut();
-                let elem_size = mem::size_of::<T>();
-
-                if self_heap.capacity != 0 && elem_size != 0 {
-                    unsafe {
-                        dealloc(
-                            self_heap.ptr.as_ptr() as *mut u8,
-                            Layout::array::<T>(self_heap.capacity).unwrap(),
-                        );
-                    }
+            if capacity != 0 && !Self::IS_ZST {
+                unsafe {
+                    self.alloc.deallocate(
+                        self.get_heap().ptr.cast(),
+                        Layout::array::<T>(capacity).unwrap(),
+                    );
                 }
             }
         }
     }
 }
 
-impl<'a, T: Copy> Extend<&'a T> for Repr<T> {
+impl<'a, T: Copy, const N: usize, A: Allocator, G: GrowthPolicy> Extend<&'a T>
+    for Repr<T, N, A, G>
+{
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for elem in iter {
             self.push(*elem);
         }
     }
 }
 
-impl<T> Extend<T> for Repr<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Extend<T> for Repr<T, N, A, G> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for elem in iter {
             self.push(elem);
         }
     }
 }
 
-impl<T: Clone> Repr<T> {
+impl<T: Clone, const N: usize, A: Allocator, G: GrowthPolicy> Repr<T, N, A, G> {
     pub fn extend_from_slice(&mut self, data: &[T]) {
-        let new_len = self.len() + data.len();
-
-        if new_len > self.capacity() {
-            self.grow_exact(data.len());
-        }
+        self.reserve(data.len());
 
         for elem in data {
             self.push(elem.clone());
         }
     }
+
+    pub fn try_extend_from_slice(&mut self, data: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(data.len())?;
+        self.extend_from_slice(data);
+        Ok(())
+    }
 }
 
-impl<T> Repr<T> {
-    pub fn new_inline(data: &[T]) -> Self {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Repr<T, N, A, G> {
+    /// Offset of the first inline element from the start of the `Repr`.
+    ///
+    /// The discriminant occupies byte 0, so this is the first offset past it
+    /// that is aligned for `T`.
+    const INLINE_OFFSET: usize = mem::align_of::<T>();
+
+    /// Number of elements that fit in the inline buffer.
+    ///
+    /// Zero-sized elements are never stored inline, see [`Repr::IS_ZST`].
+    pub const INLINE_CAPACITY: usize = {
+        assert!(
+            N <= Discriminant::MAX_LEN,
+            "inline size does not fit in the discriminant length field"
+        );
+        match mem::size_of::<T>() {
+            0 => 0,
+            size => (N + 1).saturating_sub(Self::INLINE_OFFSET) / size,
+        }
+    };
+
+    /// Zero-sized elements always use the heap representation with a dangling
+    /// pointer and [`Heap::MAX_CAPACITY`], so they never allocate and their
+    /// length is not limited by the discriminant.
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub fn new_inline(data: &[T], alloc: A, growth: G) -> Self {
         let len = data.len();
+        if Self::IS_ZST {
+            let mut repr = Self::new_heap(alloc, growth);
+            repr.set_len(len);
+            return repr;
+        }
+
         assert!(
-            mem::size_of::<T>() * len <= INLINE_SIZE,
+            len <= Self::INLINE_CAPACITY,
             "data too large to be stored inline"
         );
-        let mut inline_data: [u8; INLINE_SIZE] = unsafe { MaybeUninit::zeroed().assume_init() };
-        let inline_t: *mut [T] = unsafe { inline_data.as_mut_slice().align_to_mut::<T>().1 };
-        let data_ptr: *const [T] = data;
-        unsafe { ptr::copy_nonoverlapping(data_ptr as *const T, inline_t as *mut T, len) };
-
-        Repr {
-            inline: ManuallyDrop::new(Inline {
-                disc: Discriminant::new(true, len as u8),
-                data: inline_data,
-                _phantom: PhantomData::<T>,
-            }),
-        }
+        let mut repr = Repr {
+            _align: [],
+            _align_elem: [],
+            disc: Discriminant::inline(len),
+            data: Data {
+                inline: [MaybeUninit::uninit(); N],
+            },
+            alloc,
+            growth,
+        };
+        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), repr.inline_ptr_mut(), len) };
+
+        repr
     }
 
     #[inline]
-    pub fn from_heap(data: &[T]) -> Self {
-        let mut repr = Self::new_heap();
+    pub fn from_heap(data: &[T], alloc: A, growth: G) -> Self {
+        let mut repr = Self::new_heap(alloc, growth);
 
-        repr.grow_exact(data.len());
+        handle_reserve(repr.try_reserve_exact(data.len()));
 
         let ptr: *mut T = repr.as_ptr_mut();
         let data_ptr = data as *const [T];
-        unsafe { ptr::copy_nonoverlapping(data_ptr as *const T, ptr as *mut T, data.len()) };
+        unsafe { ptr::copy_nonoverlapping(data_ptr as *const T, ptr, data.len()) };
 
         repr.set_len(data.len());
         repr
     }
 
-    pub fn new_heap() -> Self {
+    /// Creates an empty `Repr` that can hold `capacity` elements without reallocating,
+    /// staying inline when they fit.
+    pub fn with_capacity(capacity: usize, alloc: A, growth: G) -> Self {
+        if capacity <= Self::INLINE_CAPACITY {
+            Self::new_inline(&[], alloc, growth)
+        } else {
+            let mut repr = Self::new_heap(alloc, growth);
+            handle_reserve(repr.try_reserve_exact(capacity));
+            repr
+        }
+    }
+
+    pub fn new_heap(alloc: A, growth: G) -> Self {
         let len = 0;
-        let capacity = if mem::size_of::<T>() == 0 {
-            usize::MAX
+        let capacity = if Self::IS_ZST {
+            Heap::<T>::MAX_CAPACITY
         } else {
             0
         };
         let heap_data = NonNull::dangling();
 
-        Repr {
-            heap: ManuallyDrop::new(Heap {
-                len,
-                capacity,
-                ptr: heap_data,
-            }),
-        }
+        let mut repr = Repr {
+            _align: [],
+            _align_elem: [],
+            disc: Discriminant::inline(0),
+            data: Data {
+                heap: ManuallyDrop::new(Heap {
+                    capacity: [0; WORD_SIZE - 1],
+                    len,
+                    ptr: heap_data,
+                }),
+            },
+            alloc,
+            growth,
+        };
+        repr.set_heap_capacity(capacity);
+        repr
     }
 
     pub fn get(&self, idx: usize) -> Option<&T> {
-        if idx >= self.len() {
-            None
-        } else {
-            match self.is_inline() {
-                true => Some(&self.inline_data()[idx]),
-                false => Some(unsafe { &*self.get_heap().ptr.as_ptr().add(idx) }),
-            }
-        }
+        self.as_slice().get(idx)
     }
 
     pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
-        if idx >= self.len() {
-            None
-        } else {
-            match self.is_inline() {
-                true => Some(&mut self.inline_data_mut()[idx]),
-                false => Some(unsafe { &mut *self.get_heap_mut().ptr.as_ptr().add(idx) }),
-            }
-        }
+        self.as_slice_mut().get_mut(idx)
     }
 
     pub fn as_slice(&self) -> &[T] {
-        match self.is_inline() {
-            true => &self.inline_data()[..self.len()],
-            false => unsafe {
-                &*ptr::slice_from_raw_parts(self.get_heap().ptr.as_ptr(), self.get_heap().len)
-            },
-        }
+        unsafe { &*ptr::slice_from_raw_parts(self.as_ptr(), self.len()) }
     }
 
     pub fn as_slice_mut(&mut self) -> &mut [T] {
         let len = self.len();
-        match self.is_inline() {
-            true => &mut self.inline_data_mut()[..len],
-            false => unsafe {
-                &mut *ptr::slice_from_raw_parts_mut(self.get_heap().ptr.as_ptr(), len)
-            },
-        }
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(self.as_ptr_mut(), len) }
     }
 
     pub fn push(&mut self, elem: T) {
         let new_len = self.len() + 1;
         match self.is_inline() {
             true => {
-                if new_len * mem::size_of::<T>() <= INLINE_SIZE {
+                if new_len <= Self::INLINE_CAPACITY {
                     let len = self.len();
-                    self.inline_data_mut()[len] = elem;
+                    unsafe { ptr::write(self.inline_ptr_mut().add(len), elem) };
                     self.set_len(new_len);
                 } else {
-                    self.grow();
+                    self.reserve(1);
                     self.heap_push(elem);
                 }
             }
@@ -197,6 +258,12 @@ impl<T> Repr<T> {
         }
     }
 
+    pub fn try_push(&mut self, elem: T) -> Result<(), TryReserveError> {
+        self.try_reserve(1)?;
+        self.push(elem);
+        Ok(())
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         if self.len() == 0 {
             return None;
@@ -209,67 +276,81 @@ impl<T> Repr<T> {
         Some(data)
     }
 
-    pub fn insert(&mut self, idx: usize, element: T) {
-        let len = self.len();
-        assert!(len + 1 > idx, "index is out of range");
-
-        if len == self.capacity() {
-            self.grow();
+    /// Drops the elements past `len`, keeping the capacity.
+    ///
+    /// The length is lowered before dropping, so a panicking destructor leaks
+    /// the remaining tail rather than dropping it twice.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
         }
 
-        let count = self.len() - idx;
-        let ptr: *mut T = self.as_ptr_mut();
-
-        if idx < len {
-            unsafe {
-                ptr::copy_nonoverlapping(ptr.add(idx), ptr.add(idx + 1), count);
-            }
-        } else if idx == len {
-            // no shift
-        } else {
-            unreachable!();
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_ptr_mut().add(len), old_len - len);
+            self.set_len(len);
+            ptr::drop_in_place(tail);
         }
+    }
+
+    pub fn insert(&mut self, idx: usize, element: T) {
+        let len = self.len();
+        assert!(idx <= len, "index is out of range");
 
-        let dst_ptr: *mut T = unsafe { self.as_ptr_mut().add(idx) };
+        self.reserve(1);
 
         unsafe {
-            ptr::write(dst_ptr, element);
+            let ptr: *mut T = self.as_ptr_mut().add(idx);
+            // the ranges overlap whenever more than one element is shifted
+            ptr::copy(ptr, ptr.add(1), len - idx);
+            ptr::write(ptr, element);
         }
 
         self.set_len(len + 1);
     }
 
+    pub fn try_insert(&mut self, idx: usize, element: T) -> Result<(), TryReserveError> {
+        assert!(idx <= self.len(), "index is out of range");
+
+        self.try_reserve(1)?;
+        self.insert(idx, element);
+        Ok(())
+    }
+
     pub fn remove(&mut self, idx: usize) -> T {
         let len = self.len();
+        assert!(idx < len, "index out of range");
 
-        assert!(len >= idx, "index out of range");
-
-        let count = self.len() - idx - 1;
-        let ptr: *const T = self.as_ptr();
+        let elem = unsafe {
+            let ptr: *mut T = self.as_ptr_mut().add(idx);
+            let elem = ptr::read(ptr);
+            ptr::copy(ptr.add(1), ptr, len - idx - 1);
+            elem
+        };
 
-        let elem = unsafe { ptr::read(ptr) };
+        self.set_len(len - 1);
+        elem
+    }
 
-        // shuffle
+    /// Removes the element at `idx` by moving the last element into its place.
+    pub fn swap_remove(&mut self, idx: usize) -> T {
+        let len = self.len();
+        assert!(idx < len, "index out of range");
 
-        let ptr: *mut T = self.as_ptr_mut();
-        if idx < len {
-            unsafe {
-                ptr::copy(ptr.add(idx + 1), ptr.add(idx), count);
-            }
-        } else if idx == len {
-            // no shift
-        } else {
-            unreachable!();
-        }
+        let elem = unsafe {
+            let ptr: *mut T = self.as_ptr_mut();
+            let elem = ptr::read(ptr.add(idx));
+            ptr::copy(ptr.add(len - 1), ptr.add(idx), 1);
+            elem
+        };
 
         self.set_len(len - 1);
-
         elem
     }
 
     pub fn as_ptr(&self) -> *const T {
         match self.is_inline() {
-            true => (self.inline_data() as *const [T]) as *const T,
+            true => self.inline_ptr(),
             false => {
                 let self_heap = self.get_heap();
                 self_heap.ptr.as_ptr()
@@ -279,17 +360,17 @@ impl<T> Repr<T> {
 
     pub fn as_ptr_mut(&mut self) -> *mut T {
         match self.is_inline() {
-            true => (self.inline_data_mut() as *mut [T]) as *mut T,
+            true => self.inline_ptr_mut(),
             false => {
                 let self_heap = self.get_heap_mut();
-                self_heap.ptr.as_ptr() as *mut T
+                self_heap.ptr.as_ptr()
             }
         }
     }
 
     pub fn heap_push(&mut self, elem: T) {
         if self.len() == self.capacity() {
-            self.grow();
+            self.reserve(1);
         }
 
         let self_heap = self.get_heap_mut();
@@ -301,147 +382,336 @@ impl<T> Repr<T> {
         self_heap.len += 1;
     }
 
+    /// Makes room for at least `additional` more elements, as sized by the growth policy.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        if self.capacity() - self.len() < additional {
+            handle_reserve(self.try_reserve(additional));
+        }
+    }
+
+    /// Makes room for exactly `additional` more elements.
     #[inline]
-    fn grow(&mut self) {
-        // grow by 2x when unspecified
-        self.grow_exact(self.capacity());
+    pub fn reserve_exact(&mut self, additional: usize) {
+        if self.capacity() - self.len() < additional {
+            handle_reserve(self.try_reserve_exact(additional));
+        }
     }
 
-    fn grow_exact(&mut self, grow_by: usize) {
-        assert!(mem::size_of::<T>() != 0); // don't grow for zst
+    /// Makes room for at least `additional` more elements, as sized by the growth policy.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveErrorKind::CapacityOverflow)?;
 
-        let (new_cap, new_layout) = if self.capacity() == 0 {
-            let new_cap = grow_by.max(1);
-            (new_cap, Layout::array::<T>(new_cap).unwrap())
-        } else {
-            let new_cap = self.capacity() + grow_by;
-            let new_layout = Layout::array::<T>(new_cap).unwrap();
+        if required <= self.capacity() {
+            return Ok(());
+        }
 
-            (new_cap, new_layout)
-        };
+        let new_cap = self
+            .growth
+            .new_capacity(self.capacity(), required)
+            .max(required);
+        self.try_grow_exact(new_cap - self.capacity())
+    }
 
-        if self.is_inline() && new_cap * mem::size_of::<T>() <= INLINE_SIZE {
-            return;
+    /// Makes room for exactly `additional` more elements.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+        if required <= self.capacity() {
+            return Ok(());
         }
 
-        assert!(
-            new_layout.size() <= isize::MAX as usize,
-            "Allocation too large"
-        );
+        self.try_grow_exact(required - self.capacity())
+    }
 
-        let new_ptr = if self.capacity() == 0 {
-            match self.is_inline() {
-                true => {
-                    // grow from stack to heap
-                    self.inline_to_heap(new_cap)
-                }
-                false => unsafe { alloc(new_layout) },
-            }
+    fn try_grow_exact(&mut self, grow_by: usize) -> Result<(), TryReserveError> {
+        let new_cap = if self.capacity() == 0 {
+            grow_by.max(1)
         } else {
-            match self.is_inline() {
-                true => {
-                    // grow from stack to heap
-                    self.inline_to_heap(new_cap)
-                }
-                false => {
-                    // grow from heap to heap
-                    let self_heap = self.get_heap_mut();
-
-                    let old_layout = Layout::array::<T>(self_heap.capacity).unwrap();
-                    let old_ptr = self_heap.ptr.as_ptr() as *mut u8;
-                    unsafe { realloc(old_ptr, old_layout, new_layout.size()) }
-                }
-            }
+            self.capacity()
+                .checked_add(grow_by)
+                .ok_or(TryReserveErrorKind::CapacityOverflow)?
         };
 
-        let self_heap = self.get_heap_mut();
+        if self.is_inline() && new_cap <= Self::INLINE_CAPACITY {
+            return Ok(());
+        }
+
+        // zero-sized elements start out at the maximum capacity, so growing them always overflows
+        if new_cap > Heap::<T>::MAX_CAPACITY {
+            return Err(TryReserveErrorKind::CapacityOverflow.into());
+        }
+        let new_layout =
+            Layout::array::<T>(new_cap).map_err(|_| TryReserveErrorKind::CapacityOverflow)?;
 
-        self_heap.ptr = match NonNull::new(new_ptr as *mut T) {
-            Some(p) => p,
-            None => handle_alloc_error(new_layout),
+        if self.is_inline() {
+            // grow from stack to heap
+            return self.try_inline_to_heap(new_cap);
+        }
+
+        let new_ptr = if self.capacity() == 0 {
+            self.alloc.allocate(new_layout)
+        } else {
+            // grow from heap to heap
+            let old_layout = Layout::array::<T>(self.capacity()).unwrap();
+            let old_ptr = self.get_heap().ptr.cast();
+            unsafe { self.alloc.grow(old_ptr, old_layout, new_layout) }
         };
-        self_heap.capacity = new_cap;
+
+        self.get_heap_mut().ptr = new_ptr
+            .map_err(|_| TryReserveErrorKind::AllocError { layout: new_layout })?
+            .cast();
+        self.set_heap_capacity(new_cap);
+        Ok(())
     }
 
-    fn inline_to_heap(&mut self, new_capacity: usize) -> *mut u8 {
+    fn try_inline_to_heap(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
         assert!(self.is_inline());
 
-        let new_self = Self::new_heap();
         let len = self.len();
+        let new_layout =
+            Layout::array::<T>(new_capacity).map_err(|_| TryReserveErrorKind::CapacityOverflow)?;
+        let ptr: NonNull<T> = self
+            .alloc
+            .allocate(new_layout)
+            .map_err(|_| TryReserveErrorKind::AllocError { layout: new_layout })?
+            .cast();
+
+        unsafe {
+            ptr::copy_nonoverlapping(self.inline_ptr(), ptr.as_ptr(), len.min(new_capacity));
+        }
 
-        let old_self: Self = mem::replace(self, new_self);
+        self.data = Data {
+            heap: ManuallyDrop::new(Heap {
+                capacity: [0; WORD_SIZE - 1],
+                len,
+                ptr,
+            }),
+        };
+        self.set_heap_capacity(new_capacity);
+        Ok(())
+    }
 
-        let self_heap = self.get_heap_mut();
+    /// Shrinks the capacity as close to `max(len, min_capacity)` as possible,
+    /// moving the elements back inline and freeing the heap buffer when they fit.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        if self.is_inline() || Self::IS_ZST {
+            return;
+        }
+
+        let target = self.len().max(min_capacity);
+        if target >= self.capacity() {
+            return;
+        }
 
-        self_heap.len = len;
-        self_heap.capacity = new_capacity; // overwritten again later
+        if target <= Self::INLINE_CAPACITY {
+            self.heap_to_inline();
+            return;
+        }
 
-        let new_layout = Layout::array::<T>(new_capacity).unwrap();
-        let ptr = unsafe { alloc(new_layout) } as *mut T;
-        let data_ptr: *const [T] = old_self.inline_data();
+        let old_layout = Layout::array::<T>(self.capacity()).unwrap();
+        let new_layout = Layout::array::<T>(target).unwrap();
+        let old_ptr = self.get_heap().ptr.cast();
+        let new_ptr = unsafe { self.alloc.shrink(old_ptr, old_layout, new_layout) };
 
+        match new_ptr {
+            Ok(ptr) => self.get_heap_mut().ptr = ptr.cast(),
+            Err(_) => handle_alloc_error(new_layout),
+        }
+        self.set_heap_capacity(target);
+    }
+
+    /// Moves the elements back inline if they fit, freeing the heap buffer.
+    ///
+    /// Returns whether the `Repr` is inline afterwards.
+    pub fn try_inline(&mut self) -> bool {
+        if !self.is_inline() && !Self::IS_ZST && self.len() <= Self::INLINE_CAPACITY {
+            self.heap_to_inline();
+        }
+        self.is_inline()
+    }
+
+    /// Moves the elements onto the heap into a buffer of exactly `len` elements.
+    ///
+    /// An empty inline `Repr` becomes an empty heap `Repr` with a dangling pointer
+    /// and no capacity, so no zero-sized allocation is ever made.
+    pub fn spill(&mut self) {
+        if !self.is_inline() {
+            return;
+        }
+
+        match self.len() {
+            0 => {
+                self.data = Data {
+                    heap: ManuallyDrop::new(Heap {
+                        capacity: [0; WORD_SIZE - 1],
+                        len: 0,
+                        ptr: NonNull::dangling(),
+                    }),
+                };
+                self.set_heap_capacity(0);
+            }
+            len => handle_reserve(self.try_inline_to_heap(len)),
+        }
+    }
+
+    /// Decomposes a spilled `Repr` into its pointer, length, capacity and allocator.
+    ///
+    /// Inline contents are spilled first. The caller becomes responsible for the buffer.
+    pub fn into_raw_parts(mut self) -> (NonNull<T>, usize, usize, A) {
+        self.spill();
+
+        let this = ManuallyDrop::new(self);
+        let heap = this.get_heap();
+        let parts = (heap.ptr, heap.len, this.capacity());
         unsafe {
-            ptr::copy_nonoverlapping(data_ptr as *const T, ptr as *mut T, len);
+            drop(ptr::read(&this.growth));
+            (parts.0, parts.1, parts.2, ptr::read(&this.alloc))
         }
+    }
 
-        mem::forget(old_self);
-        ptr as *mut u8
+    /// Reassembles a heap `Repr` from the parts returned by [`Repr::into_raw_parts`].
+    ///
+    /// # Safety
+    ///
+    /// Unless `capacity` is zero or `T` is zero-sized, `ptr` must have been allocated
+    /// by `alloc` with `Layout::array::<T>(capacity)`, which is what `Drop` deallocates.
+    /// The first `len` elements must be initialized and `len <= capacity`.
+    pub unsafe fn from_raw_parts(
+        ptr: NonNull<T>,
+        len: usize,
+        capacity: usize,
+        alloc: A,
+        growth: G,
+    ) -> Self {
+        debug_assert!(Self::IS_ZST || len <= capacity);
+
+        let mut repr = Self::new_heap(alloc, growth);
+        if !Self::IS_ZST {
+            repr.set_heap_capacity(capacity);
+        }
+        repr.get_heap_mut().ptr = ptr;
+        repr.set_len(len);
+        repr
     }
 
-    #[inline]
-    pub fn bytes_mut(&mut self) -> &mut [u8] {
-        match self.is_inline() {
-            true => {
-                let len = self.len();
-                unsafe { mem::transmute(&mut self.get_inline_mut().data[..len]) }
+    fn heap_to_inline(&mut self) {
+        let len = self.len();
+        assert!(len <= Self::INLINE_CAPACITY);
+
+        debug_assert!(!Self::IS_ZST);
+        let capacity = self.capacity();
+        let ptr = self.get_heap().ptr;
+
+        self.data = Data {
+            inline: [MaybeUninit::uninit(); N],
+        };
+        self.disc = Discriminant::inline(len);
+
+        unsafe {
+            ptr::copy_nonoverlapping(ptr.as_ptr(), self.inline_ptr_mut(), len);
+            if capacity != 0 {
+                self.alloc
+                    .deallocate(ptr.cast(), Layout::array::<T>(capacity).unwrap());
             }
-            false => {
-                let len = self.get_heap().len;
-                unsafe {
-                    mem::transmute(&mut *ptr::slice_from_raw_parts_mut(
-                        self.get_heap_mut().ptr.as_ptr(),
-                        len,
-                    ))
-                }
+        }
+    }
+
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    #[inline]
+    pub fn growth_policy(&self) -> &G {
+        &self.growth
+    }
+
+    /// Replaces the growth policy, keeping the contents and allocation.
+    pub fn with_growth_policy<H: GrowthPolicy>(self, growth: H) -> Repr<T, N, A, H> {
+        let this = mem::ManuallyDrop::new(self);
+        unsafe {
+            drop(ptr::read(&this.growth));
+            Repr {
+                _align: [],
+                _align_elem: [],
+                disc: this.disc,
+                data: ptr::read(&this.data),
+                alloc: ptr::read(&this.alloc),
+                growth,
             }
         }
     }
 
+    #[inline]
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.len() * mem::size_of::<T>();
+        let ptr = self.as_ptr_mut() as *mut u8;
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(ptr, len) }
+    }
+
     #[inline]
     pub fn bytes(&self) -> &[u8] {
-        match self.is_inline() {
-            true => unsafe { mem::transmute(&self.get_inline().data[..self.len()]) },
-            false => unsafe {
-                mem::transmute(&*ptr::slice_from_raw_parts(
-                    self.get_heap().ptr.as_ptr(),
-                    self.get_heap().len,
-                ))
-            },
-        }
+        let len = self.len() * mem::size_of::<T>();
+        let ptr = self.as_ptr() as *const u8;
+        unsafe { &*ptr::slice_from_raw_parts(ptr, len) }
     }
 
     #[inline]
     pub fn capacity(&self) -> usize {
         match self.is_inline() {
-            true => INLINE_SIZE / mem::size_of::<T>(),
-            false => self.get_heap().capacity,
+            true => Self::INLINE_CAPACITY,
+            false => {
+                let mut word = [self.disc.0.get(); WORD_SIZE];
+                word[1..].copy_from_slice(&self.get_heap().capacity);
+                usize::from_be_bytes(word) & !Heap::<T>::MARKER
+            }
         }
     }
 
+    /// Stores a heap capacity, which also marks the `Repr` as a heap representation.
+    #[inline]
+    fn set_heap_capacity(&mut self, capacity: usize) {
+        assert!(capacity <= Heap::<T>::MAX_CAPACITY, "capacity overflow");
+
+        let word = (capacity | Heap::<T>::MARKER).to_be_bytes();
+        unsafe { (*self.data.heap).capacity.copy_from_slice(&word[1..]) };
+        self.disc = Discriminant(unsafe { NonZeroU8::new_unchecked(word[0]) });
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         match self.is_inline() {
-            true => self.get_inline().disc.len(),
+            true => self.disc.len(),
             false => self.get_heap().len,
         }
     }
 
+    /// Returns the uninitialized slots between the length and the capacity.
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = self.len();
+        let spare = self.capacity() - len;
+        unsafe {
+            &mut *ptr::slice_from_raw_parts_mut(
+                self.as_ptr_mut().add(len) as *mut MaybeUninit<T>,
+                spare,
+            )
+        }
+    }
+
     #[inline]
     pub fn set_len(&mut self, len: usize) {
         match self.is_inline() {
             true => {
-                assert!(len * mem::size_of::<T>() <= INLINE_SIZE);
-                self.get_inline_mut().disc.set_len(len as u8)
+                assert!(len <= Self::INLINE_CAPACITY);
+                self.disc.set_len(len)
             }
             false => {
                 #[cfg(target_pointer_width = "64")]
@@ -454,97 +724,112 @@ impl<T> Repr<T> {
     }
 
     #[inline]
-    pub fn inline_data(&self) -> &[T] {
-        let self_inline = self.get_inline();
-        unsafe { self_inline.data.as_slice().align_to().1 }
+    fn inline_ptr(&self) -> *const T {
+        let base = self as *const Self as *const u8;
+        unsafe { base.add(Self::INLINE_OFFSET) as *const T }
     }
 
     #[inline]
-    pub fn inline_data_mut(&mut self) -> &mut [T] {
-        let self_inline = self.get_inline_mut();
-        unsafe { self_inline.data.as_mut_slice().align_to_mut().1 }
+    fn inline_ptr_mut(&mut self) -> *mut T {
+        let base = self as *mut Self as *mut u8;
+        unsafe { base.add(Self::INLINE_OFFSET) as *mut T }
     }
 
     #[inline]
     pub fn is_inline(&self) -> bool {
-        let data = unsafe { &self.inline };
-        data.disc.variant()
-    }
-
-    #[inline]
-    fn get_inline(&self) -> &Inline<T> {
-        debug_assert!(self.is_inline());
-
-        unsafe { &self.inline }
+        self.disc.is_inline()
     }
 
     #[inline]
     fn get_heap(&self) -> &Heap<T> {
         debug_assert!(!self.is_inline());
 
-        unsafe { &self.heap }
-    }
-
-    #[inline]
-    fn get_inline_mut(&mut self) -> &mut Inline<T> {
-        debug_assert!(self.is_inline());
-
-        unsafe { &mut self.inline }
+        unsafe { &self.data.heap }
     }
 
     #[inline]
     pub(crate) fn get_heap_mut(&mut self) -> &mut Heap<T> {
         debug_assert!(!self.is_inline());
 
-        unsafe { &mut self.heap }
+        unsafe { &mut self.data.heap }
     }
 }
 
+/// Turns a failed reservation into a panic or an allocation error, for the infallible API.
+#[inline]
+pub(crate) fn handle_reserve(result: Result<(), TryReserveError>) {
+    match result.map_err(|err| err.kind()) {
+        Ok(()) => {}
+        Err(TryReserveErrorKind::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveErrorKind::AllocError { layout }) => handle_alloc_error(layout),
+    }
+}
+
+/// First byte of every `Repr`.
+///
+/// Inline representations store `len + 1`, which keeps the byte within
+/// `0x01..=0x7F`. Heap representations share the byte with the top of the
+/// capacity word, which always has [`Heap::MARKER`] set and lands within
+/// `0x80..=0xFF`. Zero is never valid.
 #[derive(Clone, Copy)]
-pub struct Discriminant(u8);
+#[repr(transparent)]
+pub struct Discriminant(NonZeroU8);
 
 impl Discriminant {
-    fn new(variant: bool, len: u8) -> Self {
-        if variant {
-            Self(len | 0b10000000)
-        } else {
-            Self(len << 1 >> 1)
-        }
+    /// Largest inline length that can be stored.
+    pub(crate) const MAX_LEN: usize = 0b01111110;
+
+    const HEAP_BIT: u8 = 0b10000000;
+
+    #[inline]
+    fn inline(len: usize) -> Self {
+        debug_assert!(len <= Self::MAX_LEN);
+        Self(unsafe { NonZeroU8::new_unchecked(len as u8 + 1) })
     }
 
     #[inline]
-    fn variant(&self) -> bool {
-        match self.0 & 0b10000000 {
-            0b10000000 => true,
-            _ => false,
-        }
+    fn is_inline(&self) -> bool {
+        self.0.get() & Self::HEAP_BIT == 0
     }
 
     #[inline]
     fn len(&self) -> usize {
-        (self.0 << 1 >> 1) as usize
+        debug_assert!(self.is_inline());
+        (self.0.get() - 1) as usize
     }
 
     #[inline]
-    fn set_len(&mut self, new_len: u8) {
-        self.0 = (self.0 & 0b10000000) | new_len;
+    fn set_len(&mut self, new_len: usize) {
+        *self = Self::inline(new_len);
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use core::mem;
 
-    use super::Discriminant;
-    use crate::{repr::Repr, Str};
+    use super::{Discriminant, Heap};
+    use crate::{allocator::Global, growth::Doubling, repr::Repr, Str, Vector, INLINE_SIZE};
 
     #[test]
     fn repr_size() {
         #[cfg(target_pointer_width = "64")]
-        assert_eq!(mem::size_of::<Repr<u32>>(), 24);
+        assert_eq!(mem::size_of::<Repr<u32, INLINE_SIZE>>(), 24);
+
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(mem::size_of::<Repr<u32, INLINE_SIZE>>(), 12);
+    }
+
+    #[test]
+    fn repr_size_custom_inline() {
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(mem::size_of::<Repr<u8, 64>>(), 72);
 
         #[cfg(target_pointer_width = "32")]
-        assert_eq!(mem::size_of::<Repr<u32>>(), 12);
+        assert_eq!(mem::size_of::<Repr<u8, 64>>(), 68);
+
+        assert_eq!(Repr::<u8, 64>::INLINE_CAPACITY, 64);
     }
 
     #[test]
@@ -557,46 +842,96 @@ mod test {
     }
 
     #[test]
-    fn discriminant() {
-        assert_eq!(Discriminant::new(true, 1).0, 0b10000001);
-        assert_eq!(Discriminant::new(false, 1).0, 0b00000001);
+    fn option_niche() {
+        assert_eq!(
+            mem::size_of::<Option<Repr<u32, INLINE_SIZE>>>(),
+            mem::size_of::<Repr<u32, INLINE_SIZE>>()
+        );
+        assert_eq!(mem::size_of::<Option<Str>>(), mem::size_of::<Str>());
+        assert_eq!(mem::size_of::<Option<Str<64>>>(), mem::size_of::<Str<64>>());
+        assert_eq!(
+            mem::size_of::<Option<Vector<u64>>>(),
+            mem::size_of::<Vector<u64>>()
+        );
+        assert_eq!(
+            mem::size_of::<Option<Vector<u16, 40>>>(),
+            mem::size_of::<Vector<u16, 40>>()
+        );
+    }
 
-        assert_eq!(Discriminant::new(true, 23).0, 0b10010111);
-        assert_eq!(Discriminant::new(false, 23).0, 0b00010111);
+    #[test]
+    fn heap_capacity_never_reads_as_inline() {
+        let mut repr = Repr::<u8, INLINE_SIZE>::new_heap(Global, Doubling);
+        for capacity in [
+            0,
+            1,
+            127,
+            128,
+            255,
+            256,
+            384,
+            0x8080,
+            Heap::<u8>::MAX_CAPACITY,
+        ] {
+            repr.set_heap_capacity(capacity);
+            assert_eq!(repr.is_inline(), false);
+            assert_eq!(repr.capacity(), capacity);
+        }
+        repr.set_heap_capacity(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn heap_capacity_limit() {
+        let mut repr = Repr::<u8, INLINE_SIZE>::new_heap(Global, Doubling);
+        repr.set_heap_capacity(Heap::<u8>::MAX_CAPACITY + 1);
+    }
+
+    #[test]
+    fn discriminant() {
+        assert_eq!(Discriminant::inline(0).0.get(), 0b00000001);
+        assert_eq!(Discriminant::inline(1).0.get(), 0b00000010);
+        assert_eq!(Discriminant::inline(23).0.get(), 0b00011000);
+        assert_eq!(
+            Discriminant::inline(Discriminant::MAX_LEN).0.get(),
+            0b01111111
+        );
     }
 
     #[test]
     fn discriminant_len() {
-        assert_eq!(Discriminant::new(true, 2).len(), 2);
-        assert_eq!(Discriminant::new(false, 2).len(), 2);
-        assert_eq!(Discriminant::new(true, 36).len(), 36);
-        assert_eq!(Discriminant::new(false, 36).len(), 36);
+        assert_eq!(Discriminant::inline(0).len(), 0);
+        assert_eq!(Discriminant::inline(2).len(), 2);
+        assert_eq!(Discriminant::inline(36).len(), 36);
+        assert_eq!(Discriminant::inline(126).len(), 126);
     }
 
     #[test]
     fn discriminant_set_len() {
-        let mut disc = Discriminant::new(true, 13);
-        disc.set_len(0);
-        assert_eq!(disc.len(), 0);
-
-        let mut disc = Discriminant::new(false, 13);
+        let mut disc = Discriminant::inline(13);
         disc.set_len(0);
         assert_eq!(disc.len(), 0);
 
-        let mut disc = Discriminant::new(true, 0);
-        disc.set_len(20);
-        assert_eq!(disc.len(), 20);
-
-        let mut disc = Discriminant::new(false, 0);
+        let mut disc = Discriminant::inline(0);
         disc.set_len(20);
         assert_eq!(disc.len(), 20);
     }
 
     #[test]
     fn discriminant_variant() {
-        assert_eq!(Discriminant::new(true, 2).variant(), true);
-        assert_eq!(Discriminant::new(false, 2).variant(), false);
-        assert_eq!(Discriminant::new(true, 36).variant(), true);
-        assert_eq!(Discriminant::new(false, 36).variant(), false);
+        assert_eq!(Discriminant::inline(2).is_inline(), true);
+        assert_eq!(Discriminant::inline(126).is_inline(), true);
+        assert_eq!(
+            Repr::<u8, INLINE_SIZE>::new_heap(Global, Doubling)
+                .disc
+                .is_inline(),
+            false
+        );
+        assert_eq!(
+            Repr::<u8, INLINE_SIZE>::new_inline(&[], Global, Doubling)
+                .disc
+                .is_inline(),
+            true
+        );
     }
 }
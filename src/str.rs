@@ -1,25 +1,206 @@
 extern crate alloc;
 
+use crate::allocator::{Allocator, Global};
+use crate::error::{FromUtf8Error, TryReserveError};
+use crate::growth::{Doubling, GrowthPolicy};
 use crate::repr::Repr;
-use crate::INLINE_SIZE;
-use core::ops::Deref;
+use crate::vec::slice_range;
+use crate::{Vector, INLINE_SIZE};
+use core::{
+    iter::FusedIterator,
+    mem,
+    ops::{Deref, Range, RangeBounds},
+    ptr::{self, NonNull},
+    slice, str,
+};
 
+/// A string storing up to `N` bytes inline before spilling onto the heap.
+///
+/// `N` can be at most 126.
+///
+/// Spilled storage is allocated with `A` and sized by `G`.
 #[repr(transparent)]
-pub struct Str(Repr<u8>);
+pub struct Str<const N: usize = INLINE_SIZE, A: Allocator = Global, G: GrowthPolicy = Doubling>(
+    Repr<u8, N, A, G>,
+);
 
 impl Str {
     #[inline]
     pub fn new() -> Self {
-        Self(Repr::<u8>::new_inline(&[]))
+        Self::new_in(Global)
+    }
+
+    /// Copies `bytes` into a new string, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    #[inline]
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+        Self::from_utf8_lossy_in(bytes, Global)
+    }
+
+    /// Creates an empty string with room for `capacity` bytes, staying inline when they fit.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
     }
 
     pub fn from(string: &str) -> Self {
-        match string.len() {
-            0..=INLINE_SIZE => Self(Repr::<u8>::new_inline(string.as_bytes())),
-            _ => Self(Repr::<u8>::from_heap(string.as_bytes())),
+        Self::from_in(string, Global)
+    }
+
+    /// Creates a heap string directly from a pointer, a length and a capacity.
+    ///
+    /// # Safety
+    ///
+    /// See [`Str::from_raw_parts_in`], with the global allocator as `alloc`.
+    /// Buffers from `alloc::string::String` meet these requirements.
+    #[inline]
+    pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> Self {
+        Self::from_raw_parts_in(buf, length, capacity, Global)
+    }
+}
+
+impl<const N: usize> Str<N> {
+    /// Decomposes the string into a pointer to its heap buffer, its length and its capacity.
+    ///
+    /// Inline bytes are first moved into a buffer of exactly `len` bytes. An
+    /// empty string may return a dangling pointer with a capacity of zero.
+    /// The buffer can be reassembled with [`Str::from_raw_parts`].
+    #[inline]
+    pub fn into_raw_parts(self) -> (*mut u8, usize, usize) {
+        let (ptr, len, capacity, _) = self.into_raw_parts_with_alloc();
+        (ptr, len, capacity)
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy + Default> Str<N, A, G> {
+    #[inline]
+    pub fn new_in(alloc: A) -> Self {
+        Self(Repr::new_inline(&[], alloc, G::default()))
+    }
+
+    pub fn from_in(string: &str, alloc: A) -> Self {
+        if string.len() <= Repr::<u8, N, A, G>::INLINE_CAPACITY {
+            Self(Repr::new_inline(string.as_bytes(), alloc, G::default()))
+        } else {
+            Self(Repr::from_heap(string.as_bytes(), alloc, G::default()))
         }
     }
 
+    /// Copies `bytes` into a new string, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn from_utf8_lossy_in(bytes: &[u8], alloc: A) -> Self {
+        let mut string = Self::with_capacity_in(bytes.len(), alloc);
+        for chunk in bytes.utf8_chunks() {
+            string.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                string.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+        string
+    }
+
+    /// Creates an empty string with room for `capacity` bytes, staying inline when they fit.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self(Repr::with_capacity(capacity, alloc, G::default()))
+    }
+
+    /// Creates a heap string directly from a pointer, a length, a capacity and an allocator.
+    ///
+    /// # Safety
+    ///
+    /// * `buf` must be non-null.
+    /// * Unless `capacity` is zero, `buf` must have been allocated by `alloc` with
+    ///   the layout `Layout::array::<u8>(capacity)`, which is the layout the
+    ///   string deallocates on drop.
+    /// * `capacity` must not exceed `isize::MAX`.
+    /// * `length` must be at most `capacity`, and the first `length` bytes must be valid UTF-8.
+    ///
+    /// Ownership of the buffer moves to the string.
+    #[inline]
+    pub unsafe fn from_raw_parts_in(
+        buf: *mut u8,
+        length: usize,
+        capacity: usize,
+        alloc: A,
+    ) -> Self {
+        Self(Repr::from_raw_parts(
+            NonNull::new_unchecked(buf),
+            length,
+            capacity,
+            alloc,
+            G::default(),
+        ))
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Str<N, A, G> {
+    /// Converts a byte vector into a string without copying, checking that it is valid UTF-8.
+    ///
+    /// On failure the vector is handed back through [`FromUtf8Error::into_bytes`].
+    pub fn from_utf8(bytes: Vector<u8, N, A, G>) -> Result<Self, FromUtf8Error<N, A, G>> {
+        match str::from_utf8(&bytes) {
+            Ok(_) => Ok(Self(bytes.into_repr())),
+            Err(error) => Err(FromUtf8Error::new(bytes, error)),
+        }
+    }
+
+    /// Converts a byte vector into a string without copying or checking it.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8.
+    #[inline]
+    pub unsafe fn from_utf8_unchecked(bytes: Vector<u8, N, A, G>) -> Self {
+        Self(bytes.into_repr())
+    }
+
+    /// Converts the string into its byte vector without copying.
+    #[inline]
+    pub fn into_bytes(self) -> Vector<u8, N, A, G> {
+        Vector::from_repr(self.0)
+    }
+
+    /// Decomposes the string into a pointer to its heap buffer, its length, its
+    /// capacity and its allocator.
+    ///
+    /// Inline bytes are first moved into a buffer of exactly `len` bytes. An
+    /// empty string may return a dangling pointer with a capacity of zero.
+    /// The buffer can be reassembled with [`Str::from_raw_parts_in`].
+    pub fn into_raw_parts_with_alloc(self) -> (*mut u8, usize, usize, A) {
+        let (ptr, len, capacity, alloc) = self.0.into_raw_parts();
+        (ptr.as_ptr(), len, capacity, alloc)
+    }
+
+    /// Consumes the string and leaks it, returning a mutable reference to its contents.
+    ///
+    /// Inline bytes are first moved onto the heap, since they would not outlive the string.
+    /// Neither the buffer nor the allocator are ever released.
+    pub fn leak<'a>(self) -> &'a mut str
+    where
+        A: 'a,
+    {
+        let (ptr, len, _, alloc) = self.into_raw_parts_with_alloc();
+        mem::forget(alloc);
+        unsafe { str::from_utf8_unchecked_mut(slice::from_raw_parts_mut(ptr, len)) }
+    }
+
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.0.allocator()
+    }
+
+    #[inline]
+    pub fn growth_policy(&self) -> &G {
+        self.0.growth_policy()
+    }
+
+    /// Switches to another growth policy, keeping the bytes and any spilled buffer.
+    #[inline]
+    pub fn with_growth_policy<H: GrowthPolicy>(self, growth: H) -> Str<N, A, H> {
+        Str(self.0.with_growth_policy(growth))
+    }
+
     #[inline]
     pub fn push(&mut self, ch: char) {
         match ch.len_utf8() {
@@ -33,9 +214,48 @@ impl Str {
         self.0.extend(string.as_bytes());
     }
 
+    /// Appends `string`, returning an error and leaving `self` untouched if it cannot grow.
+    #[inline]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), TryReserveError> {
+        self.0.try_extend_from_slice(string.as_bytes())
+    }
+
+    /// Reserves capacity for at least `additional` more bytes, as sized by the growth policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    /// Reserves capacity for exactly `additional` more bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.0.reserve_exact(additional)
+    }
+
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve(additional)
+    }
+
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve_exact(additional)
+    }
+
+    /// Removes the last character and returns it, or `None` if the string is empty.
     #[inline]
     pub fn pop(&mut self) -> Option<char> {
-        self.0.pop().map(|byte| byte as char)
+        let ch = self.chars().next_back()?;
+        self.0.set_len(self.len() - ch.len_utf8());
+        Some(ch)
     }
 
     #[inline]
@@ -43,16 +263,188 @@ impl Str {
         self.0.set_len(0);
     }
 
+    /// Shortens the string to `new_len` bytes, keeping the capacity.
+    ///
+    /// Does nothing if `new_len` is greater than the current length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a char boundary.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len));
+            self.0.set_len(new_len);
+        }
+    }
+
+    /// Inserts `ch` at byte position `idx`, shifting everything after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the length or does not lie on a char boundary.
+    #[inline]
+    pub fn insert(&mut self, idx: usize, ch: char) {
+        assert!(self.is_char_boundary(idx));
+        self.insert_bytes(idx, ch.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    /// Inserts `string` at byte position `idx`, shifting everything after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the length or does not lie on a char boundary.
+    #[inline]
+    pub fn insert_str(&mut self, idx: usize, string: &str) {
+        assert!(self.is_char_boundary(idx));
+        self.insert_bytes(idx, string.as_bytes());
+    }
+
+    fn insert_bytes(&mut self, idx: usize, bytes: &[u8]) {
+        let len = self.len();
+        let amount = bytes.len();
+        self.0.reserve(amount);
+
+        unsafe {
+            let ptr = self.0.as_ptr_mut();
+            ptr::copy(ptr.add(idx), ptr.add(idx + amount), len - idx);
+            ptr::copy_nonoverlapping(bytes.as_ptr(), ptr.add(idx), amount);
+        }
+        self.0.set_len(len + amount);
+    }
+
+    /// Removes the character starting at byte position `idx` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not smaller than the length or does not lie on a char boundary.
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = match self[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+
+        let len = self.len();
+        let next = idx + ch.len_utf8();
+        self.0.as_slice_mut().copy_within(next..len, idx);
+        self.0.set_len(len - (next - idx));
+        ch
+    }
+
+    /// Removes the bytes in `range` and returns their characters as an iterator.
+    ///
+    /// The range is removed when the iterator is dropped, even if it was not
+    /// fully consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing, its end is past the length, or either
+    /// bound does not lie on a char boundary.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, N, A, G> {
+        let Range { start, end } = slice_range(range, self.len());
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        // the iterator borrows the characters through a raw pointer, so the
+        // string can still be edited when the drain is dropped
+        let string = self as *mut Self;
+        let chars = unsafe { (*string).get_unchecked(start..end) }.chars();
+
+        Drain {
+            string,
+            start,
+            end,
+            chars,
+        }
+    }
+
+    /// Replaces the bytes in `range` with `replace_with`, growing or shrinking the
+    /// string as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing, its end is past the length, or either
+    /// bound does not lie on a char boundary.
+    pub fn replace_range<R: RangeBounds<usize>>(&mut self, range: R, replace_with: &str) {
+        let range = slice_range(range, self.len());
+        assert!(self.is_char_boundary(range.start));
+        assert!(self.is_char_boundary(range.end));
+
+        unsafe { self.as_mut_vec() }.splice(range, replace_with.bytes());
+    }
+
+    /// Keeps only the characters for which `keep` returns `true`, in order and in place.
+    ///
+    /// If `keep` panics, the characters that were not visited yet are removed.
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut keep: F) {
+        struct SetLenOnDrop<'a, const N: usize, A: Allocator, G: GrowthPolicy> {
+            string: &'a mut Str<N, A, G>,
+            idx: usize,
+            del: usize,
+        }
+
+        impl<const N: usize, A: Allocator, G: GrowthPolicy> Drop for SetLenOnDrop<'_, N, A, G> {
+            fn drop(&mut self) {
+                // everything before `idx` has been compacted and is valid UTF-8
+                self.string.0.set_len(self.idx - self.del);
+            }
+        }
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop {
+            string: self,
+            idx: 0,
+            del: 0,
+        };
+
+        while guard.idx < len {
+            // the bytes from `idx` on are untouched, so they still start with a whole char
+            let rest = &guard.string.0.as_slice()[guard.idx..len];
+            let ch = unsafe { str::from_utf8_unchecked(rest) }
+                .chars()
+                .next()
+                .unwrap();
+            let ch_len = ch.len_utf8();
+
+            if !keep(ch) {
+                guard.del += ch_len;
+            } else if guard.del > 0 {
+                let idx = guard.idx;
+                guard
+                    .string
+                    .0
+                    .as_slice_mut()
+                    .copy_within(idx..idx + ch_len, idx - guard.del);
+            }
+            guard.idx += ch_len;
+        }
+    }
+
     #[inline]
     pub fn as_bytes(&self) -> &[u8] {
         self.0.bytes()
     }
 
+    /// # Safety
+    ///
+    /// The bytes must remain valid UTF-8 once the borrow ends.
     #[inline]
     pub unsafe fn as_bytes_mut(&mut self) -> &mut [u8] {
         self.0.bytes_mut()
     }
 
+    /// Returns the underlying bytes as a `Vector`, sharing the inline buffer or
+    /// heap allocation, for example to fill the spare capacity.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must remain valid UTF-8 once the borrow ends.
+    #[inline]
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vector<u8, N, A, G> {
+        // `Vector` is a transparent wrapper around the same `Repr`
+        &mut *(&mut self.0 as *mut Repr<u8, N, A, G> as *mut Vector<u8, N, A, G>)
+    }
+
     #[inline]
     pub fn capacity(&self) -> usize {
         self.0.capacity()
@@ -63,6 +455,11 @@ impl Str {
         self.0.len()
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     #[inline]
     pub fn as_str(&self) -> &str {
         self.deref()
@@ -72,67 +469,193 @@ impl Str {
     pub fn is_inline(&self) -> bool {
         self.0.is_inline()
     }
+
+    /// Shrinks the capacity to fit the length, moving the bytes back inline
+    /// and freeing the heap buffer when they fit.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to(0)
+    }
+
+    /// Shrinks the capacity to at least `min_capacity` and the length, moving the
+    /// bytes back inline and freeing the heap buffer when they fit.
+    ///
+    /// Does nothing if the capacity is already at most `min_capacity`.
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.0.shrink_to(min_capacity)
+    }
+
+    /// Moves the bytes back inline if they fit, freeing the heap buffer.
+    ///
+    /// Returns whether the string is inline afterwards; a string that does not
+    /// fit is left untouched.
+    #[inline]
+    pub fn try_inline(&mut self) -> bool {
+        self.0.try_inline()
+    }
 }
 
-impl Clone for Str {
-    fn clone(&self) -> Self {
-        Str::from(&self)
+/// A draining iterator over the characters of a range of a [`Str`].
+///
+/// Created by [`Str::drain`].
+pub struct Drain<
+    'a,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    string: *mut Str<N, A, G>,
+    start: usize,
+    end: usize,
+    chars: str::Chars<'a>,
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Drain<'_, N, A, G> {
+    /// Returns the remaining characters as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.chars.as_str()
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> core::fmt::Debug for Drain<'_, N, A, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
     }
 }
 
-impl PartialEq for Str {
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Iterator for Drain<'_, N, A, G> {
+    type Item = char;
+
     #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        PartialEq::eq(&self[..], &other[..])
+    fn next(&mut self) -> Option<char> {
+        self.chars.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for Drain<'_, N, A, G> {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        self.chars.next_back()
+    }
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> FusedIterator for Drain<'_, N, A, G> {}
+
+unsafe impl<const N: usize, A: Allocator + Sync, G: GrowthPolicy + Sync> Sync
+    for Drain<'_, N, A, G>
+{
+}
+unsafe impl<const N: usize, A: Allocator + Send, G: GrowthPolicy + Send> Send
+    for Drain<'_, N, A, G>
+{
+}
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Drop for Drain<'_, N, A, G> {
+    fn drop(&mut self) {
+        // both bounds were checked to be char boundaries, so this keeps the string valid
+        unsafe { (*self.string).as_mut_vec() }.drain(self.start..self.end);
+    }
+}
+
+impl<const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Str<N, A, G> {
+    /// Splits the string in two at byte position `at`, returning everything from
+    /// `at` on in a new string that shares the allocator and growth policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is larger than the length or does not lie on a char boundary.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(self.is_char_boundary(at));
+        let other = self.sibling(&self[at..]);
+        self.0.set_len(at);
+        other
     }
+
+    /// Copies `string` into a new string with the same allocator and growth policy.
+    fn sibling(&self, string: &str) -> Self {
+        let mut other = Str(Repr::with_capacity(
+            string.len(),
+            self.allocator().clone(),
+            self.growth_policy().clone(),
+        ));
+        other.push_str(string);
+        other
+    }
+}
+
+impl<const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Clone for Str<N, A, G> {
+    fn clone(&self) -> Self {
+        self.sibling(self)
+    }
+}
+
+impl<const N: usize, const M: usize, A, B, G, H> PartialEq<Str<M, B, H>> for Str<N, A, G>
+where
+    A: Allocator,
+    B: Allocator,
+    G: GrowthPolicy,
+    H: GrowthPolicy,
+{
     #[inline]
-    fn ne(&self, other: &Self) -> bool {
-        PartialEq::ne(&self[..], &other[..])
+    fn eq(&self, other: &Str<M, B, H>) -> bool {
+        PartialEq::eq(&self[..], &other[..])
     }
 }
 
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Eq for Str<N, A, G> {}
+
 macro_rules! impl_eq {
     ($lhs:ty, $rhs: ty) => {
         #[allow(unused_lifetimes)]
-        impl<'a, 'b> PartialEq<$rhs> for $lhs {
+        impl<'a, 'b, const N: usize, A: Allocator, G: GrowthPolicy> PartialEq<$rhs> for $lhs {
             #[inline]
             fn eq(&self, other: &$rhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
             }
-            #[inline]
-            fn ne(&self, other: &$rhs) -> bool {
-                PartialEq::ne(&self[..], &other[..])
-            }
         }
 
         #[allow(unused_lifetimes)]
-        impl<'a, 'b> PartialEq<$lhs> for $rhs {
+        impl<'a, 'b, const N: usize, A: Allocator, G: GrowthPolicy> PartialEq<$lhs> for $rhs {
             #[inline]
             fn eq(&self, other: &$lhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
             }
-            #[inline]
-            fn ne(&self, other: &$lhs) -> bool {
-                PartialEq::ne(&self[..], &other[..])
-            }
         }
     };
 }
 
-impl_eq! { Str, str }
-impl_eq! {Str, alloc::string::String }
-impl_eq! { Str, &'a str }
-#[cfg(not(no_global_oom_handling))]
-impl_eq! { alloc::borrow::Cow< 'a,str>, Str }
+impl_eq! { Str<N, A, G>, str }
+impl_eq! { Str<N, A, G>, alloc::string::String }
+impl_eq! { Str<N, A, G>, &'a str }
+impl_eq! { alloc::borrow::Cow<'a, str>, Str<N, A, G> }
+
+impl<const N: usize, A: Allocator + Default, G: GrowthPolicy + Default> Default for Str<N, A, G> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
 
-impl AsRef<str> for Str {
+impl<const N: usize, A: Allocator, G: GrowthPolicy> AsRef<str> for Str<N, A, G> {
     #[inline]
     fn as_ref(&self) -> &str {
         self
     }
 }
 
-impl Deref for Str {
+impl<const N: usize, A: Allocator, G: GrowthPolicy> Deref for Str<N, A, G> {
     type Target = str;
 
     #[inline]
@@ -141,15 +664,115 @@ impl Deref for Str {
     }
 }
 
-impl core::fmt::Debug for Str {
+impl<const N: usize, A: Allocator, G: GrowthPolicy> core::fmt::Debug for Str<N, A, G> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Debug::fmt(&**self, f)
     }
 }
 
-impl core::fmt::Display for Str {
+impl<const N: usize, A: Allocator, G: GrowthPolicy> core::fmt::Display for Str<N, A, G> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Display::fmt(self.as_str(), f)
     }
 }
+
+impl<const N: usize, A: Allocator, G: GrowthPolicy> core::fmt::Write for Str<N, A, G> {
+    #[inline]
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        self.push_str(string);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, ch: char) -> core::fmt::Result {
+        self.push(ch);
+        Ok(())
+    }
+}
+
+/// Formats `args` straight into a [`Str`], the function behind [`format_str!`].
+///
+/// Arguments without any placeholders are copied in one go with the exact length.
+/// Everything else is written into a string pre-sized from `template`, the format
+/// string literal given to the macro, so output that fits stays inline.
+#[doc(hidden)]
+pub fn format_str(template: &str, args: core::fmt::Arguments<'_>) -> Str {
+    match args.as_str() {
+        Some(string) => Str::from(string),
+        None => {
+            let mut string = Str::with_capacity(estimated_capacity(template));
+            core::fmt::Write::write_fmt(&mut string, args)
+                .expect("a formatting trait implementation returned an error");
+            string
+        }
+    }
+}
+
+/// Estimates the length of formatted output like `format!` does, from the literal
+/// pieces of `template` with its placeholders left out.
+fn estimated_capacity(template: &str) -> usize {
+    let bytes = template.as_bytes();
+    let mut pieces_length: usize = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'}' if bytes.get(i + 1) == Some(&bytes[i]) => {
+                pieces_length += 1;
+                i += 2;
+            }
+            b'{' => {
+                while i < bytes.len() && bytes[i] != b'}' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => {
+                pieces_length += 1;
+                i += 1;
+            }
+        }
+    }
+
+    // output that starts with an argument and has little text around it is usually
+    // short, anything else gets room for the arguments on top of the text
+    let starts_with_argument = template.starts_with('{') && !template.starts_with("{{");
+    let estimate = if starts_with_argument && pieces_length < 16 {
+        0
+    } else {
+        pieces_length.saturating_mul(2)
+    };
+
+    // estimates that fit inline start there and only spill if the output needs it
+    if estimate <= INLINE_SIZE {
+        0
+    } else {
+        estimate
+    }
+}
+
+/// Like `format!`, but builds a [`Str`] without going through a `String`.
+///
+/// Short output stays inline, longer output starts from a capacity estimated
+/// from the text around the placeholders of the format string.
+#[macro_export]
+macro_rules! format_str {
+    ($template:literal $($rest:tt)*) => {
+        $crate::str::format_str($template, ::core::format_args!($template $($rest)*))
+    };
+    ($($arg:tt)*) => {
+        $crate::str::format_str("", ::core::format_args!($($arg)*))
+    };
+}
+
+/// Converts a value to a [`Str`], implemented for every `Display` type.
+pub trait ToStr {
+    fn to_str(&self) -> Str;
+}
+
+impl<T: core::fmt::Display + ?Sized> ToStr for T {
+    #[inline]
+    fn to_str(&self) -> Str {
+        format_str("", format_args!("{self}"))
+    }
+}
@@ -1,46 +1,236 @@
 extern crate alloc;
 
 use core::{
-    marker::PhantomData,
-    mem,
-    ops::{Deref, DerefMut, Index, IndexMut},
-    ptr,
-    slice::SliceIndex,
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr::{self, NonNull},
+    slice::{self, SliceIndex},
 };
 
-use alloc::fmt;
+use alloc::{boxed::Box, fmt, vec::Vec};
 
-use crate::repr::Repr;
+use crate::{
+    allocator::{Allocator, Global},
+    error::TryReserveError,
+    growth::{Doubling, GrowthPolicy},
+    repr::{handle_reserve, Repr},
+    INLINE_SIZE,
+};
 
+/// A vector storing up to `N` bytes of elements inline before spilling onto the heap.
+///
+/// Inline elements start at the first offset past the discriminant byte that is
+/// aligned for `T`, so `align_of::<T>() - 1` of those bytes go to padding and
+/// `Vector<u32, 35>` is what holds 8 `u32`s inline. `N` can be at most 126.
+///
+/// Spilled storage is allocated with `A` and sized by `G`.
 #[repr(transparent)]
-pub struct Vector<T>(Repr<T>);
+pub struct Vector<
+    T,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+>(Repr<T, N, A, G>);
 
 impl<T> Vector<T> {
     #[inline]
     pub fn new() -> Self {
-        Self(Repr::<T>::new_inline(&[]))
+        Self::new_in(Global)
+    }
+
+    /// Creates an empty vector with room for `capacity` elements, staying inline when they fit.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
     }
 
     #[inline]
     pub fn new_heap() -> Self {
-        Self(Repr::<T>::new_heap())
+        Self::new_heap_in(Global)
     }
 
     #[inline]
     pub fn from_heap(data: &[T]) -> Self {
-        Self(Repr::<T>::from_heap(data))
+        Self::from_heap_in(data, Global)
+    }
+
+    /// Creates a heap vector directly from a pointer, a length and a capacity.
+    ///
+    /// # Safety
+    ///
+    /// See [`Vector::from_raw_parts_in`], with the global allocator as `alloc`.
+    /// Buffers from `alloc::vec::Vec` meet these requirements.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Self {
+        Self::from_raw_parts_in(ptr, length, capacity, Global)
+    }
+}
+
+impl<T, const N: usize> Vector<T, N> {
+    /// Decomposes the vector into a pointer to its heap buffer, its length and its capacity.
+    ///
+    /// Inline elements are first moved into a buffer of exactly `len` elements. An
+    /// empty vector may return a dangling pointer with a capacity of zero.
+    /// The buffer can be reassembled with [`Vector::from_raw_parts`].
+    #[inline]
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let (ptr, len, capacity, _) = self.into_raw_parts_with_alloc();
+        (ptr, len, capacity)
+    }
+
+    /// Converts the vector into a boxed slice, dropping any excess capacity.
+    ///
+    /// Spilled buffers are reused, shrinking them in place when possible.
+    #[inline]
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        Vec::from(self).into_boxed_slice()
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy + Default> Vector<T, N, A, G> {
+    #[inline]
+    pub fn new_in(alloc: A) -> Self {
+        Self(Repr::new_inline(&[], alloc, G::default()))
+    }
+
+    #[inline]
+    pub fn new_heap_in(alloc: A) -> Self {
+        Self(Repr::new_heap(alloc, G::default()))
+    }
+
+    #[inline]
+    pub fn from_heap_in(data: &[T], alloc: A) -> Self {
+        Self(Repr::from_heap(data, alloc, G::default()))
+    }
+
+    /// Creates an empty vector with room for `capacity` elements, staying inline when they fit.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self(Repr::with_capacity(capacity, alloc, G::default()))
+    }
+
+    /// Creates a heap vector directly from a pointer, a length, a capacity and an allocator.
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must be non-null and aligned for `T`.
+    /// * Unless `capacity` is zero or `T` is zero-sized, `ptr` must have been
+    ///   allocated by `alloc` with the layout `Layout::array::<T>(capacity)`,
+    ///   which is the layout the vector deallocates on drop.
+    /// * `capacity` must not exceed `isize::MAX`.
+    /// * `length` must be at most `capacity`, and the first `length` elements must be initialized.
+    ///
+    /// Ownership of the buffer moves to the vector.
+    #[inline]
+    pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize, alloc: A) -> Self {
+        Self(Repr::from_raw_parts(
+            NonNull::new_unchecked(ptr),
+            length,
+            capacity,
+            alloc,
+            G::default(),
+        ))
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
+    #[inline]
+    pub(crate) fn from_repr(repr: Repr<T, N, A, G>) -> Self {
+        Self(repr)
+    }
+
+    /// Moves the storage out without dropping the elements.
+    #[inline]
+    pub(crate) fn into_repr(self) -> Repr<T, N, A, G> {
+        let this = mem::ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.0) }
+    }
+
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.0.allocator()
+    }
+
+    #[inline]
+    pub fn growth_policy(&self) -> &G {
+        self.0.growth_policy()
+    }
+
+    /// Switches to another growth policy, keeping the elements and any spilled buffer.
+    #[inline]
+    pub fn with_growth_policy<H: GrowthPolicy>(self, growth: H) -> Vector<T, N, A, H> {
+        let this = mem::ManuallyDrop::new(self);
+        Vector(unsafe { ptr::read(&this.0) }.with_growth_policy(growth))
+    }
+
+    /// Decomposes the vector into a pointer to its heap buffer, its length, its
+    /// capacity and its allocator.
+    ///
+    /// Inline elements are first moved into a buffer of exactly `len` elements. An
+    /// empty vector may return a dangling pointer with a capacity of zero.
+    /// The buffer can be reassembled with [`Vector::from_raw_parts_in`].
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        let this = mem::ManuallyDrop::new(self);
+        let (ptr, len, capacity, alloc) = unsafe { ptr::read(&this.0) }.into_raw_parts();
+        (ptr.as_ptr(), len, capacity, alloc)
+    }
+
+    /// Consumes the vector and leaks its elements, returning a mutable slice to them.
+    ///
+    /// Inline elements are first moved onto the heap, since they would not outlive the vector.
+    /// Neither the buffer nor the allocator are ever released.
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where
+        A: 'a,
+    {
+        let (ptr, len, _, alloc) = self.into_raw_parts_with_alloc();
+        mem::forget(alloc);
+        unsafe { slice::from_raw_parts_mut(ptr, len) }
     }
 
+    /// # Safety
+    ///
+    /// `T` must not contain padding bytes.
     #[inline]
     pub unsafe fn as_bytes(&self) -> &[u8] {
         self.0.bytes()
     }
 
+    /// # Safety
+    ///
+    /// `T` must not contain padding bytes, and any bytes written must form valid values of `T`.
     #[inline]
     pub unsafe fn as_bytes_mut(&mut self) -> &mut [u8] {
         self.0.bytes_mut()
     }
 
+    /// Returns the spare capacity past the length as uninitialized slots, in the
+    /// inline buffer or the heap allocation alike.
+    ///
+    /// Once slots are written, [`Vector::set_len`] makes them part of the vector.
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.0.spare_capacity_mut()
+    }
+
+    /// Sets the length without dropping or initializing any elements.
+    ///
+    /// # Safety
+    ///
+    /// * `new_len` must be at most [`Vector::capacity`].
+    /// * The elements up to `new_len` must be initialized.
+    ///
+    /// Shortening the vector this way leaks the elements past `new_len`.
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.0.set_len(new_len)
+    }
+
     #[inline]
     pub fn capacity(&self) -> usize {
         self.0.capacity()
@@ -52,7 +242,12 @@ impl<T> Vector<T> {
     }
 
     #[inline]
-    pub fn get(&mut self, idx: usize) -> Option<&T> {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&T> {
         self.0.get(idx)
     }
 
@@ -66,21 +261,148 @@ impl<T> Vector<T> {
         self.0.push(element);
     }
 
+    /// Appends an element, returning an error instead of panicking or aborting
+    /// if the vector cannot grow.
+    #[inline]
+    pub fn try_push(&mut self, element: T) -> Result<(), TryReserveError> {
+        self.0.try_push(element)
+    }
+
     #[inline]
     pub fn pop(&mut self) -> Option<T> {
         self.0.pop()
     }
 
+    /// Shortens the vector to `len` elements, dropping the rest.
+    ///
+    /// Does nothing if `len` is not below the current length. The capacity is
+    /// unchanged, so a spilled vector stays on the heap until it is shrunk.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len)
+    }
+
+    /// Drops all elements, keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.truncate(0)
+    }
+
+    /// Resizes the vector to `new_len`, filling new slots with the results of `f`
+    /// or dropping the excess elements.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        let len = self.len();
+        if new_len > len {
+            self.reserve(new_len - len);
+            for _ in len..new_len {
+                self.push(f());
+            }
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    /// Overwrites every element with the results of `f`, dropping the old elements.
+    pub fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
+        for elem in self.as_slice_mut() {
+            *elem = f();
+        }
+    }
+
+    /// Inserts an element at `idx`, shifting the following elements up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
     #[inline]
     pub fn insert(&mut self, idx: usize, element: T) {
         self.0.insert(idx, element);
     }
 
+    /// Inserts an element at `idx`, returning an error if the vector cannot grow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    #[inline]
+    pub fn try_insert(&mut self, idx: usize, element: T) -> Result<(), TryReserveError> {
+        self.0.try_insert(idx, element)
+    }
+
+    /// Reserves capacity for at least `additional` more elements, as sized by the
+    /// growth policy, so repeated calls reallocate an amortized constant number of times.
+    ///
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    /// Reserves capacity for exactly `additional` more elements, bypassing the growth policy.
+    ///
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` elements.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.0.reserve_exact(additional)
+    }
+
+    /// Reserves capacity for at least `additional` more elements, returning an
+    /// error if the capacity overflows or the allocator fails.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve(additional)
+    }
+
+    /// Reserves capacity for exactly `additional` more elements, returning an
+    /// error if the capacity overflows or the allocator fails.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.0.try_reserve_exact(additional)
+    }
+
+    /// Removes and returns the element at `idx`, shifting the following elements down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= len`.
     #[inline]
     pub fn remove(&mut self, idx: usize) -> T {
         self.0.remove(idx)
     }
 
+    /// Removes and returns the element at `idx`, replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= len`.
+    #[inline]
+    pub fn swap_remove(&mut self, idx: usize) -> T {
+        self.0.swap_remove(idx)
+    }
+
+    /// Inserts the elements of `iter` at `idx`, shifting the following elements up.
+    ///
+    /// The tail is moved once if `iter` reports an exact size hint, see [`Vector::splice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    #[inline]
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, idx: usize, iter: I) {
+        assert!(idx <= self.len(), "index is out of range");
+        self.splice(idx..idx, iter);
+    }
+
     #[inline]
     pub fn as_slice(&self) -> &[T] {
         self.0.as_slice()
@@ -96,6 +418,31 @@ impl<T> Vector<T> {
         self.0.is_inline()
     }
 
+    /// Shrinks the capacity to fit the length, moving the elements back inline
+    /// and freeing the heap buffer when they fit.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to(0)
+    }
+
+    /// Shrinks the capacity to at least `min_capacity` and the length, moving the
+    /// elements back inline and freeing the heap buffer when they fit.
+    ///
+    /// Does nothing if the capacity is already at most `min_capacity`.
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.0.shrink_to(min_capacity)
+    }
+
+    /// Moves the elements back inline if they fit, freeing the heap buffer.
+    ///
+    /// Returns whether the vector is inline afterwards; a vector that does not
+    /// fit is left untouched.
+    #[inline]
+    pub fn try_inline(&mut self) -> bool {
+        self.0.try_inline()
+    }
+
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.as_slice().iter()
@@ -106,69 +453,348 @@ impl<T> Vector<T> {
         self.as_slice_mut().iter_mut()
     }
 
+    /// Removes the elements in `range`, returning them as an iterator.
+    ///
+    /// Elements the iterator does not yield are dropped with it, after which the
+    /// elements following the range are moved back to close the gap. The vector is
+    /// shortened to the start of the range up front, so leaking the iterator leaks
+    /// the drained elements and the tail but is otherwise safe.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the length.
     #[inline]
-    pub fn drain(&mut self) -> Drain<T> {
-        unsafe {
-            let start = self.0.as_ptr_mut();
-            let end = start.add(self.len());
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, N, A, G> {
+        let len = self.len();
+        let Range { start, end } = slice_range(range, len);
+        self.0.set_len(start);
+
+        Drain {
+            start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            vec: self,
+        }
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
+    /// Replaces the elements in `range` with `replace_with`, returning the removed
+    /// elements as an iterator.
+    ///
+    /// The replacement happens when the iterator is dropped, even if it was not
+    /// consumed. The tail is moved at most twice, or once if the lower bound of
+    /// `replace_with`'s size hint is exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the length.
+    #[inline]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, N, A, G>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Returns an iterator removing and yielding the elements in `range` for which
+    /// `filter` returns `true`.
+    ///
+    /// Elements are visited in order and may be modified by `filter` whether or
+    /// not they are removed. Elements that are not visited, because the iterator
+    /// was dropped early or `filter` panicked, are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the length.
+    pub fn extract_if<R, F>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F, N, A, G>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len();
+        let Range { start, end } = slice_range(range, old_len);
+        // the iterator owns the elements now, leaking them if it is forgotten
+        self.0.set_len(0);
+
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            filter,
+        }
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
+    /// Keeps only the elements for which `keep` returns `true`, in order and in place.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        self.retain_mut(|elem| keep(elem))
+    }
 
-            Drain {
-                phantom: PhantomData,
-                start,
-                end,
+    /// Keeps only the elements for which `keep` returns `true`, in order and in place,
+    /// allowing `keep` to modify them.
+    ///
+    /// Removed elements are dropped as they are visited. If `keep` or a destructor
+    /// panics, the elements that were not visited yet are kept.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut keep: F) {
+        self.extract_if(.., |elem| !keep(elem)).for_each(drop);
+    }
+
+    /// Removes consecutive elements for which `same_bucket` returns `true`.
+    ///
+    /// `same_bucket` is passed each element and the last element kept before it,
+    /// and removes the former when it returns `true`. If `same_bucket` or a
+    /// destructor panics, the elements that were not visited yet are kept.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        /// Closes the gap between the kept and unvisited elements, even when unwinding.
+        struct FillGap<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> {
+            vec: &'a mut Vector<T, N, A, G>,
+            read: usize,
+            write: usize,
+            len: usize,
+        }
+
+        impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for FillGap<'_, T, N, A, G> {
+            fn drop(&mut self) {
+                unsafe {
+                    let ptr = self.vec.0.as_ptr_mut();
+                    ptr::copy(
+                        ptr.add(self.read),
+                        ptr.add(self.write),
+                        self.len - self.read,
+                    );
+                }
+                self.vec.0.set_len(self.write + self.len - self.read);
+            }
+        }
+
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        let mut gap = FillGap {
+            vec: self,
+            read: 1,
+            write: 1,
+            len,
+        };
+        let ptr = gap.vec.0.as_ptr_mut();
+
+        while gap.read < len {
+            unsafe {
+                let read = ptr.add(gap.read);
+                let prev = ptr.add(gap.write - 1);
+
+                if same_bucket(&mut *read, &mut *prev) {
+                    // advance first so a panicking destructor does not drop it again
+                    gap.read += 1;
+                    ptr::drop_in_place(read);
+                } else {
+                    ptr::copy(read, ptr.add(gap.write), 1);
+                    gap.write += 1;
+                    gap.read += 1;
+                }
             }
         }
     }
+
+    /// Removes consecutive elements that map to the same key.
+    #[inline]
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
 }
 
-impl<T: Clone> Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
+    /// Moves all elements of `other` to the end of this vector with a single copy,
+    /// leaving `other` empty but keeping its capacity.
+    pub fn append(&mut self, other: &mut Self) {
+        let count = other.len();
+        self.reserve(count);
+
+        let len = self.len();
+        unsafe {
+            ptr::copy_nonoverlapping(other.0.as_ptr(), self.0.as_ptr_mut().add(len), count);
+            other.0.set_len(0);
+        }
+        self.0.set_len(len + count);
+    }
+}
+
+impl<T, const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Vector<T, N, A, G> {
+    /// Splits the vector in two at `at`, returning the elements from `at` onwards.
+    ///
+    /// The returned vector shares the allocator and growth policy, and is inline
+    /// when the elements fit. This vector keeps its capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+
+        let count = len - at;
+        let mut other = Vector(Repr::with_capacity(
+            count,
+            self.allocator().clone(),
+            self.growth_policy().clone(),
+        ));
+        unsafe {
+            ptr::copy_nonoverlapping(self.0.as_ptr().add(at), other.0.as_ptr_mut(), count);
+        }
+        self.0.set_len(at);
+        other.0.set_len(count);
+        other
+    }
+}
+
+impl<T: PartialEq, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    #[inline]
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+impl<T: Clone, const N: usize, A: Allocator, G: GrowthPolicy> Vector<T, N, A, G> {
     #[inline]
     pub fn extend_from_slice(&mut self, data: &[T]) {
         self.0.extend_from_slice(data)
     }
+
+    /// Resizes the vector to `new_len`, filling new slots with clones of `value`
+    /// or dropping the excess elements.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        let len = self.len();
+        if new_len > len {
+            self.reserve(new_len - len);
+            for _ in len + 1..new_len {
+                self.push(value.clone());
+            }
+            self.push(value);
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    /// Overwrites every element with clones of `value`, dropping the old elements.
+    ///
+    /// The last element takes `value` itself, so no clone is left over to drop.
+    pub fn fill(&mut self, value: T) {
+        if let Some((last, rest)) = self.as_slice_mut().split_last_mut() {
+            for elem in rest {
+                *elem = value.clone();
+            }
+            *last = value;
+        }
+    }
+
+    /// Clones the elements of `data` into the vector at `idx`, shifting the
+    /// following elements up once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    #[inline]
+    pub fn insert_from_slice(&mut self, idx: usize, data: &[T]) {
+        self.insert_many(idx, data.iter().cloned())
+    }
+
+    /// Clones the elements in `src` and appends them to the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or its end is past the length.
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, src: R) {
+        let range = slice_range(src, self.len());
+        self.reserve(range.len());
+
+        for idx in range {
+            let elem = self[idx].clone();
+            self.push(elem);
+        }
+    }
+
+    /// Clones and appends all elements of `data`, returning an error and leaving
+    /// the vector untouched if it cannot grow.
+    #[inline]
+    pub fn try_extend_from_slice(&mut self, data: &[T]) -> Result<(), TryReserveError> {
+        self.0.try_extend_from_slice(data)
+    }
 }
 
-impl<T: fmt::Debug> fmt::Debug for Vector<T> {
+impl<T: fmt::Debug, const N: usize, A: Allocator, G: GrowthPolicy> fmt::Debug
+    for Vector<T, N, A, G>
+{
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
 }
 
-impl<T: Clone> Clone for Vector<T> {
+impl<T: Clone, const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Clone
+    for Vector<T, N, A, G>
+{
     fn clone(&self) -> Self {
-        let mut vec = Vector::<T>::new();
-        vec.extend_from_slice(&self);
+        let mut vec = Vector(Repr::new_inline(
+            &[],
+            self.allocator().clone(),
+            self.growth_policy().clone(),
+        ));
+        vec.extend_from_slice(self);
         vec
     }
 }
 
-impl<T> Drop for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for Vector<T, N, A, G> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        unsafe { ptr::drop_in_place(self.as_slice_mut()) }
     }
 }
 
-unsafe impl<T: Send> Send for Vector<T> {}
-unsafe impl<T: Sync> Sync for Vector<T> {}
-
-unsafe impl<T: Send> Send for IntoIter<T> {}
-unsafe impl<T: Sync> Sync for IntoIter<T> {}
+unsafe impl<T: Send, const N: usize, A: Allocator + Send, G: GrowthPolicy + Send> Send
+    for Vector<T, N, A, G>
+{
+}
+unsafe impl<T: Sync, const N: usize, A: Allocator + Sync, G: GrowthPolicy + Sync> Sync
+    for Vector<T, N, A, G>
+{
+}
 
-unsafe impl<'a, T: Send> Send for Drain<'a, T> {}
-unsafe impl<'a, T: Sync> Sync for Drain<'a, T> {}
+unsafe impl<T: Send, const N: usize, A: Allocator + Send, G: GrowthPolicy + Send> Send
+    for IntoIter<T, N, A, G>
+{
+}
+unsafe impl<T: Sync, const N: usize, A: Allocator + Sync, G: GrowthPolicy + Sync> Sync
+    for IntoIter<T, N, A, G>
+{
+}
 
 impl<T: Clone> From<&[T]> for Vector<T> {
     fn from(value: &[T]) -> Self {
-        let mut vec = Vector::<T>::new();
+        let mut vec = Vector::new();
         vec.extend_from_slice(value);
         vec
     }
 }
 
-impl<T, const N: usize> From<[T; N]> for Vector<T> {
-    fn from(value: [T; N]) -> Self {
-        let mut vec = Vector::<T>::new();
+impl<T, const M: usize> From<[T; M]> for Vector<T> {
+    fn from(value: [T; M]) -> Self {
+        let mut vec = Vector::new();
         vec.extend(value);
         vec
     }
@@ -181,36 +807,74 @@ impl<T: Clone> From<&mut [T]> for Vector<T> {
     }
 }
 
-impl<T> From<alloc::vec::Vec<T>> for Vector<T> {
-    fn from(value: alloc::vec::Vec<T>) -> Self {
-        let mut vec = Vector::new();
-        for elem in value {
-            vec.push(elem)
+/// Adopts the allocation of `value`, unless its elements fit inline.
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(value: Vec<T>) -> Self {
+        let len = value.len();
+
+        if len <= Repr::<T, INLINE_SIZE>::INLINE_CAPACITY {
+            let mut value = value;
+            let mut vec = Vector::new();
+            unsafe {
+                ptr::copy_nonoverlapping(value.as_ptr(), vec.0.as_ptr_mut(), len);
+                vec.0.set_len(len);
+                value.set_len(0);
+            }
+            vec
+        } else {
+            let mut value = mem::ManuallyDrop::new(value);
+            unsafe { Vector::from_raw_parts(value.as_mut_ptr(), len, value.capacity()) }
         }
-        vec
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+/// Adopts the allocation of `value`, unless its elements fit inline.
+impl<T> From<Box<[T]>> for Vector<T> {
+    #[inline]
+    fn from(value: Box<[T]>) -> Self {
+        Vec::from(value).into()
+    }
+}
+
+/// Hands over the heap buffer, spilling inline elements into a buffer of exactly `len` elements.
+impl<T, const N: usize> From<Vector<T, N>> for Vec<T> {
+    #[inline]
+    fn from(value: Vector<T, N>) -> Self {
+        let (ptr, len, capacity) = value.into_raw_parts();
+        unsafe { Vec::from_raw_parts(ptr, len, capacity) }
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> DoubleEndedIterator
+    for IntoIter<T, N, A, G>
+{
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             None
         } else {
             unsafe {
                 self.end -= 1;
-                Some(ptr::read(self.vec.0.as_ptr_mut().add(self.end)))
+                Some(ptr::read(self.vec.0.as_ptr().add(self.end)))
             }
         }
     }
 }
 
-pub struct IntoIter<T> {
-    vec: Vector<T>,
+/// An owning iterator over the elements of a [`Vector`].
+///
+/// Created by [`Vector::into_iter`].
+pub struct IntoIter<
+    T,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    vec: Vector<T, N, A, G>,
     start: usize,
     end: usize,
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Iterator for IntoIter<T, N, A, G> {
     type Item = T;
 
     #[inline]
@@ -220,107 +884,471 @@ impl<T> Iterator for IntoIter<T> {
         } else {
             let next = self.start;
             self.start += 1;
-            Some(unsafe { ptr::read(self.vec.as_mut_ptr().add(next)) })
+            Some(unsafe { ptr::read(self.vec.0.as_ptr().add(next)) })
         }
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let elem_size = mem::size_of::<T>();
-        let len = if elem_size == 0 { 1 } else { elem_size };
+        let len = self.end - self.start;
         (len, Some(len))
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> ExactSizeIterator for IntoIter<T, N, A, G> {
     fn len(&self) -> usize {
-        self.vec.len()
+        self.end - self.start
     }
 }
 
-impl<T> Drop for IntoIter<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> IntoIter<T, N, A, G> {
+    /// Returns the elements that have not been yielded yet.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            &*ptr::slice_from_raw_parts(self.vec.0.as_ptr().add(self.start), self.end - self.start)
+        }
+    }
+
+    /// Returns the elements that have not been yielded yet.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            &mut *ptr::slice_from_raw_parts_mut(
+                self.vec.0.as_ptr_mut().add(self.start),
+                self.end - self.start,
+            )
+        }
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> FusedIterator for IntoIter<T, N, A, G> {}
+
+impl<T: Clone, const N: usize, A: Allocator + Clone, G: GrowthPolicy + Clone> Clone
+    for IntoIter<T, N, A, G>
+{
+    fn clone(&self) -> Self {
+        let mut vec = Vector(Repr::with_capacity(
+            self.len(),
+            self.vec.allocator().clone(),
+            self.vec.growth_policy().clone(),
+        ));
+        vec.extend_from_slice(self.as_slice());
+        vec.into_iter()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, A: Allocator, G: GrowthPolicy> fmt::Debug
+    for IntoIter<T, N, A, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for IntoIter<T, N, A, G> {
     fn drop(&mut self) {
-        for _ in self {}
+        // the vector only frees the buffer, its length was set to zero up front
+        let remaining: *mut [T] = self.as_mut_slice();
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(remaining) }
     }
 }
 
-impl<T> IntoIterator for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> IntoIterator for Vector<T, N, A, G> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, N, A, G>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let end = self.len();
+        // the iterator owns the elements now, the vector only keeps the buffer alive
+        self.0.set_len(0);
 
-    fn into_iter(self) -> Self::IntoIter {
         IntoIter {
-            end: self.len(),
             vec: self,
             start: 0,
+            end,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> IntoIterator for &'a Vector<T, N, A, G> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> IntoIterator
+    for &'a mut Vector<T, N, A, G>
+{
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice_mut().iter_mut()
+    }
+}
+
+/// A draining iterator over a range of a [`Vector`].
+///
+/// Created by [`Vector::drain`].
+pub struct Drain<
+    'a,
+    T: 'a,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    vec: &'a mut Vector<T, N, A, G>,
+    /// Remaining elements to yield.
+    start: usize,
+    end: usize,
+    /// Elements following the drained range, to be moved back on drop.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> Drain<'a, T, N, A, G> {
+    /// Returns the elements that have not been yielded yet.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            &*ptr::slice_from_raw_parts(self.vec.0.as_ptr().add(self.start), self.end - self.start)
+        }
+    }
+
+    /// Moves the tail back to directly follow the retained elements.
+    fn move_tail(&mut self) {
+        let start = self.vec.len();
+        if self.tail_len > 0 {
+            unsafe {
+                let ptr = self.vec.0.as_ptr_mut();
+                if self.tail_start != start {
+                    ptr::copy(ptr.add(self.tail_start), ptr.add(start), self.tail_len);
+                }
+            }
+            self.vec.0.set_len(start + self.tail_len);
         }
     }
 }
 
-pub struct Drain<'a, T: 'a> {
-    phantom: PhantomData<&'a mut Vector<T>>,
-    start: *const T,
-    end: *const T,
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> Drain<'a, T, N, A, G> {
+    /// Fills the gap between the retained elements and the tail from `replace_with`,
+    /// returning whether the gap was filled completely.
+    fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
+        for idx in self.vec.len()..self.tail_start {
+            match replace_with.next() {
+                Some(element) => unsafe {
+                    ptr::write(self.vec.0.as_ptr_mut().add(idx), element);
+                    self.vec.0.set_len(idx + 1);
+                },
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Moves the tail `additional` slots further back, growing the vector if needed.
+    fn move_tail_by(&mut self, additional: usize) {
+        // the gap is filled, so the tail directly follows the elements and is
+        // counted in the length while growing, which keeps it when spilling
+        let len = self.vec.len();
+        debug_assert_eq!(len, self.tail_start);
+        self.vec.0.set_len(len + self.tail_len);
+        let result = self.vec.0.try_reserve(additional);
+        self.vec.0.set_len(len);
+        handle_reserve(result);
+
+        let new_tail_start = self.tail_start + additional;
+        unsafe {
+            let ptr = self.vec.0.as_ptr_mut();
+            ptr::copy(
+                ptr.add(self.tail_start),
+                ptr.add(new_tail_start),
+                self.tail_len,
+            );
+        }
+        self.tail_start = new_tail_start;
+    }
 }
 
-impl<'a, T> Iterator for Drain<'a, T> {
+impl<'a, T: fmt::Debug, const N: usize, A: Allocator, G: GrowthPolicy> fmt::Debug
+    for Drain<'a, T, N, A, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> Iterator for Drain<'a, T, N, A, G> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             None
         } else {
-            unsafe {
-                if mem::size_of::<T>() == 0 {
-                    self.end = self.end.wrapping_sub(1);
-                    Some(mem::zeroed())
-                } else {
-                    let old_ptr = self.start;
-                    self.start = self.start.add(1);
-                    Some(ptr::read(old_ptr))
-                }
-            }
+            let next = self.start;
+            self.start += 1;
+            Some(unsafe { ptr::read(self.vec.0.as_ptr().add(next)) })
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let elem_size = mem::size_of::<T>();
-        let len =
-            (self.end as usize - self.start as usize) / if elem_size == 0 { 1 } else { elem_size };
+        let len = self.end - self.start;
         (len, Some(len))
     }
 }
 
-impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> DoubleEndedIterator
+    for Drain<'a, T, N, A, G>
+{
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.start == self.end {
             None
         } else {
+            self.end -= 1;
+            Some(unsafe { ptr::read(self.vec.0.as_ptr().add(self.end)) })
+        }
+    }
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> ExactSizeIterator
+    for Drain<'a, T, N, A, G>
+{
+}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> FusedIterator for Drain<'a, T, N, A, G> {}
+
+impl<'a, T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for Drain<'a, T, N, A, G> {
+    fn drop(&mut self) {
+        /// Moves the tail back even if dropping an element panics.
+        struct TailGuard<'r, 'a, T, const N: usize, A: Allocator, G: GrowthPolicy>(
+            &'r mut Drain<'a, T, N, A, G>,
+        );
+
+        impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Drop for TailGuard<'_, '_, T, N, A, G> {
+            fn drop(&mut self) {
+                self.0.move_tail();
+            }
+        }
+
+        let remaining = unsafe {
+            ptr::slice_from_raw_parts_mut(
+                self.vec.0.as_ptr_mut().add(self.start),
+                self.end - self.start,
+            )
+        };
+        self.start = self.end;
+
+        let guard = TailGuard(self);
+        unsafe { ptr::drop_in_place(remaining) };
+        drop(guard);
+    }
+}
+
+/// An iterator that replaces a range of a [`Vector`], yielding the removed elements.
+///
+/// Created by [`Vector::splice`].
+pub struct Splice<
+    'a,
+    I: Iterator + 'a,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    drain: Drain<'a, I::Item, N, A, G>,
+    replace_with: I,
+}
+
+impl<'a, I: Iterator, const N: usize, A: Allocator, G: GrowthPolicy> Iterator
+    for Splice<'a, I, N, A, G>
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, I: Iterator, const N: usize, A: Allocator, G: GrowthPolicy> DoubleEndedIterator
+    for Splice<'a, I, N, A, G>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'a, I: Iterator, const N: usize, A: Allocator, G: GrowthPolicy> ExactSizeIterator
+    for Splice<'a, I, N, A, G>
+{
+}
+
+impl<'a, I: Iterator, const N: usize, A: Allocator, G: GrowthPolicy> Drop
+    for Splice<'a, I, N, A, G>
+{
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+
+        if self.drain.tail_len == 0 {
+            self.drain.vec.extend(self.replace_with.by_ref());
+            return;
+        }
+
+        if !self.drain.fill(&mut self.replace_with) {
+            return;
+        }
+
+        let (lower_bound, _) = self.replace_with.size_hint();
+        if lower_bound > 0 {
+            self.drain.move_tail_by(lower_bound);
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+        }
+
+        let mut collected = self
+            .replace_with
+            .by_ref()
+            .collect::<Vec<I::Item>>()
+            .into_iter();
+        if collected.len() > 0 {
+            self.drain.move_tail_by(collected.len());
+            let filled = self.drain.fill(&mut collected);
+            debug_assert!(filled);
+        }
+        // the drain moves the tail back to the end of the replacement when dropped
+    }
+}
+
+/// An iterator that removes and yields the elements of a [`Vector`] matching a predicate.
+///
+/// Created by [`Vector::extract_if`].
+pub struct ExtractIf<
+    'a,
+    T,
+    F: FnMut(&mut T) -> bool,
+    const N: usize = INLINE_SIZE,
+    A: Allocator = Global,
+    G: GrowthPolicy = Doubling,
+> {
+    vec: &'a mut Vector<T, N, A, G>,
+    /// Next element to visit.
+    idx: usize,
+    /// End of the range to visit.
+    end: usize,
+    /// Number of elements removed so far.
+    del: usize,
+    old_len: usize,
+    filter: F,
+}
+
+impl<'a, T, F, const N: usize, A: Allocator, G: GrowthPolicy> Iterator
+    for ExtractIf<'a, T, F, N, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.idx < self.end {
+            let i = self.idx;
             unsafe {
-                self.end = self.end.wrapping_sub(1);
-                Some(ptr::read(self.end))
+                let ptr = self.vec.0.as_ptr_mut();
+                let extract = (self.filter)(&mut *ptr.add(i));
+                // only advance once the filter returned, so a panic keeps the element
+                self.idx += 1;
+
+                if extract {
+                    self.del += 1;
+                    return Some(ptr::read(ptr.add(i)));
+                } else if self.del > 0 {
+                    ptr::copy_nonoverlapping(ptr.add(i), ptr.add(i - self.del), 1);
+                }
             }
         }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
     }
 }
 
-impl<'a, T> ExactSizeIterator for Drain<'a, T> {}
+impl<'a, T, F, const N: usize, A: Allocator, G: GrowthPolicy> FusedIterator
+    for ExtractIf<'a, T, F, N, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+{
+}
 
-impl<'a, T> Drop for Drain<'a, T> {
+impl<'a, T, F, const N: usize, A: Allocator, G: GrowthPolicy> Drop for ExtractIf<'a, T, F, N, A, G>
+where
+    F: FnMut(&mut T) -> bool,
+{
     fn drop(&mut self) {
-        for _ in self {}
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let ptr = self.vec.0.as_ptr_mut();
+                ptr::copy(
+                    ptr.add(self.idx),
+                    ptr.add(self.idx - self.del),
+                    self.old_len - self.idx,
+                );
+            }
+        }
+        self.vec.0.set_len(self.old_len - self.del);
     }
 }
 
-impl<T> FromIterator<T> for Vector<T> {
+/// Resolves `range` against a slice of length `len`, with the panics of slice indexing.
+pub(crate) fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    start..end
+}
+
+impl<T, const N: usize> FromIterator<T> for Vector<T, N> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut vec = Vector::new();
+        let mut vec = Vector::new_in(Global);
         vec.extend(iter);
         vec
     }
 }
 
-impl<T, U> PartialEq<&[U]> for Vector<T>
+impl<T, U, const N: usize, A: Allocator, G: GrowthPolicy> PartialEq<&[U]> for Vector<T, N, A, G>
 where
     T: PartialEq<U>,
 {
@@ -330,38 +1358,90 @@ where
     }
 }
 
-impl<T, U, const N: usize> PartialEq<&[U; N]> for Vector<T>
+impl<T, U, const N: usize, const M: usize, A: Allocator, G: GrowthPolicy> PartialEq<&[U; M]>
+    for Vector<T, N, A, G>
 where
     T: PartialEq<U>,
 {
     #[inline]
-    fn eq(&self, other: &&[U; N]) -> bool {
+    fn eq(&self, other: &&[U; M]) -> bool {
         self[..] == other[..]
     }
 }
 
-impl<T, U, const N: usize> PartialEq<[U; N]> for Vector<T>
+impl<T, U, const N: usize, const M: usize, A: Allocator, G: GrowthPolicy> PartialEq<[U; M]>
+    for Vector<T, N, A, G>
 where
     T: PartialEq<U>,
 {
     #[inline]
-    fn eq(&self, other: &[U; N]) -> bool {
+    fn eq(&self, other: &[U; M]) -> bool {
         self[..] == other[..]
     }
 }
-impl<T, U> PartialEq<Vector<U>> for Vector<T>
+impl<
+        T,
+        U,
+        const N: usize,
+        const M: usize,
+        A: Allocator,
+        B: Allocator,
+        G: GrowthPolicy,
+        H: GrowthPolicy,
+    > PartialEq<Vector<U, M, B, H>> for Vector<T, N, A, G>
 where
     T: PartialEq<U>,
 {
     #[inline]
-    fn eq(&self, other: &Vector<U>) -> bool {
+    fn eq(&self, other: &Vector<U, M, B, H>) -> bool {
         self[..] == other[..]
     }
 }
 
-impl<T: Eq> Eq for Vector<T> {}
+impl<T: Eq, const N: usize, A: Allocator, G: GrowthPolicy> Eq for Vector<T, N, A, G> {}
+
+impl<T, const N: usize, const M: usize, A, B, G, H> PartialOrd<Vector<T, M, B, H>>
+    for Vector<T, N, A, G>
+where
+    T: PartialOrd,
+    A: Allocator,
+    B: Allocator,
+    G: GrowthPolicy,
+    H: GrowthPolicy,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Vector<T, M, B, H>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, const N: usize, A: Allocator, G: GrowthPolicy> Ord for Vector<T, N, A, G> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+/// Hashes exactly like the equivalent `[T]`, so a `&[T]` can look up a `Vector` key.
+impl<T: Hash, const N: usize, A: Allocator, G: GrowthPolicy> Hash for Vector<T, N, A, G> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T, const N: usize, A: Allocator + Default, G: GrowthPolicy + Default> Default
+    for Vector<T, N, A, G>
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
 
-impl<T, I: SliceIndex<[T]>> Index<I> for Vector<T> {
+impl<T, I: SliceIndex<[T]>, const N: usize, A: Allocator, G: GrowthPolicy> Index<I>
+    for Vector<T, N, A, G>
+{
     type Output = I::Output;
 
     #[inline]
@@ -370,28 +1450,51 @@ impl<T, I: SliceIndex<[T]>> Index<I> for Vector<T> {
     }
 }
 
-impl<T, I: SliceIndex<[T]>> IndexMut<I> for Vector<T> {
+impl<T, I: SliceIndex<[T]>, const N: usize, A: Allocator, G: GrowthPolicy> IndexMut<I>
+    for Vector<T, N, A, G>
+{
     #[inline]
     fn index_mut(&mut self, index: I) -> &mut Self::Output {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 
-impl<T> DerefMut for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> DerefMut for Vector<T, N, A, G> {
     #[inline]
     fn deref_mut(&mut self) -> &mut <Self as Deref>::Target {
         self.as_slice_mut()
     }
 }
 
-impl<T> AsRef<[T]> for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> AsRef<[T]> for Vector<T, N, A, G> {
     #[inline]
     fn as_ref(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-impl<T> Deref for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> AsMut<[T]> for Vector<T, N, A, G> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Borrow<[T]> for Vector<T, N, A, G> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> BorrowMut<[T]> for Vector<T, N, A, G> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
+}
+
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Deref for Vector<T, N, A, G> {
     type Target = [T];
 
     #[inline]
@@ -400,14 +1503,16 @@ impl<T> Deref for Vector<T> {
     }
 }
 
-impl<T> Extend<T> for Vector<T> {
+impl<T, const N: usize, A: Allocator, G: GrowthPolicy> Extend<T> for Vector<T, N, A, G> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         Extend::extend(&mut self.0, iter)
     }
 }
 
-impl<'a, T: Copy> Extend<&'a T> for Vector<T> {
+impl<'a, T: Copy, const N: usize, A: Allocator, G: GrowthPolicy> Extend<&'a T>
+    for Vector<T, N, A, G>
+{
     #[inline]
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.0.extend(iter)
@@ -0,0 +1,361 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+
+use demon_core::{AllocError, Allocator, Global, Str, TryReserveErrorKind, Vector, INLINE_SIZE};
+
+#[derive(Default)]
+struct Counting {
+    allocs: Cell<usize>,
+    grows: Cell<usize>,
+    shrinks: Cell<usize>,
+    deallocs: Cell<usize>,
+    live_bytes: Cell<usize>,
+}
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        self.allocs.set(self.allocs.get() + 1);
+        self.live_bytes.set(self.live_bytes.get() + layout.size());
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocs.set(self.deallocs.get() + 1);
+        self.live_bytes.set(self.live_bytes.get() - layout.size());
+        Global.deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        self.grows.set(self.grows.get() + 1);
+        self.live_bytes
+            .set(self.live_bytes.get() - old_layout.size() + new_layout.size());
+        Global.grow(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        self.shrinks.set(self.shrinks.get() + 1);
+        self.live_bytes
+            .set(self.live_bytes.get() - old_layout.size() + new_layout.size());
+        Global.shrink(ptr, old_layout, new_layout)
+    }
+}
+
+/// Fails every allocation after the first `budget` bytes.
+struct Limited {
+    budget: Cell<usize>,
+}
+
+unsafe impl Allocator for Limited {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if layout.size() > self.budget.get() {
+            return Err(AllocError);
+        }
+        self.budget.set(self.budget.get() - layout.size());
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.budget.set(self.budget.get() + layout.size());
+        Global.deallocate(ptr, layout)
+    }
+}
+
+/// Pads every block with guard bytes and checks them when the block is released.
+#[derive(Default)]
+struct Guarded {
+    checked: Cell<usize>,
+}
+
+impl Guarded {
+    const GUARD: usize = 64;
+    const PATTERN: u8 = 0xA5;
+
+    fn padded(layout: Layout) -> Layout {
+        Layout::from_size_align(layout.size() + Self::GUARD, layout.align()).unwrap()
+    }
+}
+
+unsafe impl Allocator for Guarded {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let ptr = Global.allocate(Self::padded(layout))?;
+        unsafe {
+            ptr.as_ptr()
+                .add(layout.size())
+                .write_bytes(Self::PATTERN, Self::GUARD);
+        }
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        let guard = core::slice::from_raw_parts(ptr.as_ptr().add(layout.size()), Self::GUARD);
+        assert!(
+            guard.iter().all(|&byte| byte == Self::PATTERN),
+            "write past the end of a {} byte block",
+            layout.size()
+        );
+        self.checked.set(self.checked.get() + 1);
+        Global.deallocate(ptr, Self::padded(layout))
+    }
+}
+
+#[test]
+fn vector_inline_does_not_allocate() {
+    let counting = Counting::default();
+    let mut vec: Vector<u8, INLINE_SIZE, &Counting> = Vector::new_in(&counting);
+    vec.extend(0..INLINE_SIZE as u8);
+
+    assert_eq!(vec.is_inline(), true);
+    drop(vec);
+
+    assert_eq!(counting.allocs.get(), 0);
+    assert_eq!(counting.deallocs.get(), 0);
+}
+
+#[test]
+fn vector_spill_uses_allocator() {
+    let counting = Counting::default();
+    let mut vec: Vector<u32, INLINE_SIZE, &Counting> = Vector::new_in(&counting);
+    for i in 0..100 {
+        vec.push(i);
+    }
+
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(counting.allocs.get(), 1);
+    assert!(counting.grows.get() > 0);
+    assert_eq!(
+        counting.live_bytes.get(),
+        vec.capacity() * core::mem::size_of::<u32>()
+    );
+    assert!(vec.iter().copied().eq(0..100));
+
+    drop(vec);
+    assert_eq!(counting.deallocs.get(), 1);
+    assert_eq!(counting.live_bytes.get(), 0);
+}
+
+#[test]
+fn vector_with_capacity_in() {
+    let counting = Counting::default();
+
+    let vec: Vector<u8, INLINE_SIZE, &Counting> = Vector::with_capacity_in(4, &counting);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(counting.allocs.get(), 0);
+
+    let vec: Vector<u8, INLINE_SIZE, &Counting> = Vector::with_capacity_in(100, &counting);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.capacity(), 100);
+    assert_eq!(counting.allocs.get(), 1);
+    assert_eq!(counting.live_bytes.get(), 100);
+
+    drop(vec);
+    assert_eq!(counting.live_bytes.get(), 0);
+}
+
+#[test]
+fn vector_clone_shares_allocator() {
+    let counting = Counting::default();
+    let vec: Vector<u64, INLINE_SIZE, &Counting> = Vector::from_heap_in(&[1, 2, 3], &counting);
+    let cl = vec.clone();
+
+    assert_eq!(cl, vec);
+    assert_eq!(counting.allocs.get(), 2);
+
+    drop(vec);
+    drop(cl);
+    assert_eq!(counting.live_bytes.get(), 0);
+}
+
+#[test]
+fn vector_shrink_uses_allocator() {
+    let counting = Counting::default();
+    let mut vec: Vector<u8, INLINE_SIZE, &Counting> = Vector::new_in(&counting);
+    vec.extend(0..200);
+
+    while vec.len() > 100 {
+        vec.pop();
+    }
+    vec.shrink_to_fit();
+    assert_eq!(counting.shrinks.get(), 1);
+    assert_eq!(counting.live_bytes.get(), 100);
+
+    while vec.len() > 3 {
+        vec.pop();
+    }
+    vec.shrink_to_fit();
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec, [0, 1, 2]);
+    assert_eq!(counting.deallocs.get(), 1);
+    assert_eq!(counting.live_bytes.get(), 0);
+
+    drop(vec);
+    assert_eq!(counting.deallocs.get(), 1);
+}
+
+#[test]
+fn str_try_inline_frees_buffer() {
+    let counting = Counting::default();
+    let mut s: Str<INLINE_SIZE, &Counting> =
+        Str::from_in("this string is too long to be stored inline", &counting);
+    assert!(counting.live_bytes.get() > 0);
+
+    s.clear();
+    s.push_str("tiny");
+    assert_eq!(s.try_inline(), true);
+    assert_eq!(counting.live_bytes.get(), 0);
+    assert_eq!(counting.allocs.get(), counting.deallocs.get());
+    assert_eq!(s, "tiny");
+}
+
+#[test]
+fn vector_raw_parts_keep_allocator() {
+    let counting = Counting::default();
+    let vec: Vector<u8, INLINE_SIZE, &Counting> = Vector::from_heap_in(&[1, 2, 3], &counting);
+
+    let (ptr, len, cap, alloc) = vec.into_raw_parts_with_alloc();
+    assert_eq!(counting.deallocs.get(), 0);
+
+    let vec: Vector<u8, INLINE_SIZE, &Counting> =
+        unsafe { Vector::from_raw_parts_in(ptr, len, cap, alloc) };
+    assert_eq!(vec, [1, 2, 3]);
+
+    drop(vec);
+    assert_eq!(counting.deallocs.get(), 1);
+    assert_eq!(counting.live_bytes.get(), 0);
+}
+
+#[test]
+fn reserve_avoids_reallocation() {
+    let counting = Counting::default();
+    let mut vec: Vector<u32, INLINE_SIZE, &Counting> = Vector::new_in(&counting);
+    vec.reserve(1000);
+    vec.extend(0..1000);
+    assert_eq!(counting.allocs.get(), 1);
+    assert_eq!(counting.grows.get(), 0);
+
+    let mut s: Str<INLINE_SIZE, &Counting> = Str::new_in(&counting);
+    s.reserve_exact(100);
+    for _ in 0..10 {
+        s.push_str("0123456789");
+    }
+    assert_eq!(s.capacity(), 100);
+    assert_eq!(counting.allocs.get(), 2);
+    assert_eq!(counting.grows.get(), 0);
+}
+
+#[test]
+fn str_uses_allocator() {
+    let counting = Counting::default();
+
+    let short: Str<INLINE_SIZE, &Counting> = Str::from_in("short", &counting);
+    assert_eq!(short.is_inline(), true);
+    assert_eq!(counting.allocs.get(), 0);
+
+    let mut long: Str<INLINE_SIZE, &Counting> = Str::new_in(&counting);
+    long.push_str("this string is too long to be stored inline");
+    assert_eq!(long.is_inline(), false);
+    assert_eq!(long, "this string is too long to be stored inline");
+    assert!(counting.allocs.get() > 0);
+
+    drop(short);
+    drop(long);
+    assert_eq!(counting.live_bytes.get(), 0);
+    assert_eq!(counting.allocs.get(), counting.deallocs.get());
+}
+
+#[test]
+fn vector_try_push_alloc_error() {
+    let limited = Limited {
+        budget: Cell::new(0),
+    };
+    let mut vec: Vector<u8, INLINE_SIZE, &Limited> = Vector::new_in(&limited);
+
+    for i in 0..INLINE_SIZE as u8 {
+        assert_eq!(vec.try_push(i), Ok(()));
+    }
+
+    let err = vec.try_push(0xFF).unwrap_err();
+    assert!(matches!(err.kind(), TryReserveErrorKind::AllocError { .. }));
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.len(), INLINE_SIZE);
+    assert!(vec.iter().copied().eq(0..INLINE_SIZE as u8));
+}
+
+#[test]
+fn vector_try_grow_heap_alloc_error() {
+    let limited = Limited {
+        budget: Cell::new(64),
+    };
+    let mut vec: Vector<u8, INLINE_SIZE, &Limited> = Vector::new_in(&limited);
+
+    let data: Vec<u8> = (0..40).collect();
+    assert_eq!(vec.try_extend_from_slice(&data), Ok(()));
+    assert_eq!(vec.is_inline(), false);
+
+    let more: Vec<u8> = (40..200).collect();
+    let err = vec.try_extend_from_slice(&more).unwrap_err();
+    assert!(matches!(err.kind(), TryReserveErrorKind::AllocError { .. }));
+    assert_eq!(vec, data.as_slice());
+
+    assert_eq!(vec.try_insert(40, 40), Ok(()));
+    let err = vec.try_reserve_exact(1000).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        TryReserveErrorKind::AllocError { layout } if layout.size() == 1041
+    ));
+}
+
+#[test]
+fn str_try_push_str_alloc_error() {
+    let limited = Limited {
+        budget: Cell::new(0),
+    };
+    let mut s: Str<INLINE_SIZE, &Limited> = Str::new_in(&limited);
+
+    assert_eq!(s.try_push_str("fits"), Ok(()));
+    let err = s
+        .try_push_str(" but this part certainly does not")
+        .unwrap_err();
+    assert!(matches!(err.kind(), TryReserveErrorKind::AllocError { .. }));
+    assert_eq!(s, "fits");
+}
+
+#[test]
+fn spill_stays_in_bounds() {
+    let guarded = Guarded::default();
+
+    let mut vec: Vector<u8, INLINE_SIZE, &Guarded> = Vector::new_in(&guarded);
+    vec.extend([1, 2, 3]);
+    let (ptr, len, capacity, alloc) = vec.into_raw_parts_with_alloc();
+    assert_eq!((len, capacity), (3, 3));
+    let vec = unsafe {
+        Vector::<u8, INLINE_SIZE, &Guarded>::from_raw_parts_in(ptr, len, capacity, alloc)
+    };
+    assert_eq!(vec, [1, 2, 3]);
+    drop(vec);
+
+    let string: Str<INLINE_SIZE, &Guarded> = Str::from_in("abc", &guarded);
+    let (ptr, len, capacity, alloc) = string.into_raw_parts_with_alloc();
+    drop(unsafe { Str::<INLINE_SIZE, &Guarded>::from_raw_parts_in(ptr, len, capacity, alloc) });
+
+    let mut vec: Vector<u8, INLINE_SIZE, &Guarded> = Vector::new_in(&guarded);
+    vec.extend(0..10);
+    vec.splice(2..4, 100..130);
+    assert_eq!(vec.len(), 38);
+    assert!(vec[..2].iter().copied().eq(0..2));
+    assert!(vec[2..32].iter().copied().eq(100..130));
+    assert!(vec[32..].iter().copied().eq(4..10));
+    drop(vec);
+
+    assert_eq!(guarded.checked.get(), 3);
+}
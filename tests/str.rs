@@ -1,6 +1,8 @@
+#![allow(clippy::bool_assert_comparison)]
+
 extern crate alloc;
 
-use demon_core::Str;
+use demon_core::{Global, Str, TryReserveErrorKind, Vector, INLINE_SIZE};
 use proptest::prelude::*;
 
 #[test]
@@ -125,3 +127,443 @@ fn display() {
     assert_eq!(&format!("{s}"), "Test String");
     assert_eq!(format!("{s}"), format!("{}", "Test String"));
 }
+
+#[test]
+fn custom_inline_size() {
+    let text = "a/fairly/long/path/segment/that/would/spill/by/default";
+    let s: Str<64> = Str::from_in(text, Global);
+
+    assert_eq!(s.is_inline(), true);
+    assert_eq!(s.capacity(), 64);
+    assert_eq!(&s, text);
+
+    let s: Str = Str::from(text);
+    assert_eq!(s.is_inline(), false);
+}
+
+#[test]
+fn try_push_str() {
+    let mut s: Str = Str::from("short");
+    assert_eq!(s.try_push_str(" and"), Ok(()));
+    assert_eq!(s.is_inline(), true);
+    assert_eq!(s.try_push_str(" now long enough to spill"), Ok(()));
+    assert_eq!(s.is_inline(), false);
+    assert_eq!(s, "short and now long enough to spill");
+
+    let err = s.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+    assert_eq!(s, "short and now long enough to spill");
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut s: Str = Str::new();
+    s.push_str("a string long enough to spill onto the heap");
+    assert_eq!(s.is_inline(), false);
+
+    s.clear();
+    s.push_str("short again");
+    s.shrink_to_fit();
+    assert_eq!(s.is_inline(), true);
+    assert_eq!(s, "short again");
+
+    let mut s = <Str>::from("a string long enough to spill onto the heap");
+    s.push_str("!");
+    s.shrink_to(60);
+    assert_eq!(s.capacity(), 60);
+    s.shrink_to_fit();
+    assert_eq!(s.capacity(), s.len());
+    assert_eq!(s, "a string long enough to spill onto the heap!");
+}
+
+#[test]
+fn try_inline() {
+    let mut s = <Str>::from("a string long enough to spill onto the heap");
+    assert_eq!(s.try_inline(), false);
+    assert_eq!(s.is_inline(), false);
+
+    s.clear();
+    s.push_str("fits");
+    assert_eq!(s.try_inline(), true);
+    assert_eq!(s, "fits");
+}
+
+#[test]
+fn raw_parts_round_trip() {
+    let s = <Str>::from("inline");
+    let (ptr, len, cap) = s.into_raw_parts();
+    assert_eq!((len, cap), (6, 6));
+
+    let mut s = unsafe { <Str>::from_raw_parts(ptr, len, cap) };
+    assert_eq!(s.is_inline(), false);
+    s.push_str(" no more");
+    assert_eq!(s, "inline no more");
+
+    let std_string = core::mem::ManuallyDrop::new(String::from("from std"));
+    let mut s = unsafe {
+        <Str>::from_raw_parts(
+            std_string.as_ptr() as *mut u8,
+            std_string.len(),
+            std_string.capacity(),
+        )
+    };
+    s.push('!');
+    assert_eq!(s, "from std!");
+
+    let (ptr, len, cap) = s.into_raw_parts();
+    let std_string = unsafe { String::from_raw_parts(ptr, len, cap) };
+    assert_eq!(std_string, "from std!");
+}
+
+#[test]
+fn leak() {
+    let leaked: &'static mut str = <Str>::from("short").leak();
+    leaked.make_ascii_uppercase();
+    assert_eq!(leaked, "SHORT");
+
+    let leaked = <Str>::from("a string long enough to spill onto the heap").leak();
+    assert_eq!(leaked, "a string long enough to spill onto the heap");
+}
+
+#[test]
+fn with_capacity_and_reserve() {
+    let s = <Str>::with_capacity(4);
+    assert_eq!(s.is_inline(), true);
+
+    let mut s = <Str>::with_capacity(64);
+    assert_eq!(s.is_inline(), false);
+    assert_eq!(s.capacity(), 64);
+    s.push_str("a string long enough to spill onto the heap");
+    assert_eq!(s.capacity(), 64);
+
+    s.reserve(64);
+    assert_eq!(s.capacity(), 128);
+    s.reserve_exact(200);
+    assert_eq!(s.capacity(), s.len() + 200);
+}
+
+#[test]
+fn as_mut_vec() {
+    let mut s = <Str>::from("abc");
+    unsafe {
+        let vec = s.as_mut_vec();
+        vec.extend_from_slice(b"def");
+        vec[0] = b'A';
+    }
+    assert_eq!(s, "Abcdef");
+    assert_eq!(s.is_inline(), true);
+
+    unsafe {
+        let vec = s.as_mut_vec();
+        vec.reserve(40);
+        let spare = vec.spare_capacity_mut();
+        for slot in &mut spare[..40] {
+            slot.write(b'x');
+        }
+        vec.set_len(vec.len() + 40);
+    }
+    assert_eq!(s.is_inline(), false);
+    assert_eq!(s.len(), 46);
+    assert!(s.ends_with(&"x".repeat(40)));
+}
+
+#[test]
+fn default() {
+    let string: Str = Default::default();
+    assert!(string.is_empty());
+    assert!(string.is_inline());
+}
+
+#[test]
+fn pop_multibyte() {
+    let mut string = Str::from("hé€😀");
+    assert_eq!(string.pop(), Some('😀'));
+    assert_eq!(string.pop(), Some('€'));
+    assert_eq!(string.as_str(), "hé");
+    assert_eq!(string.pop(), Some('é'));
+    assert_eq!(string.pop(), Some('h'));
+    assert_eq!(string.pop(), None);
+}
+
+#[test]
+fn truncate_insert_remove() {
+    let mut string = Str::from("héllo");
+    string.truncate(10);
+    assert_eq!(string, "héllo");
+    string.truncate(3);
+    assert_eq!(string, "hé");
+
+    string.insert(1, 'ö');
+    string.insert(0, '¿');
+    string.insert_str(string.len(), " wörld and a long tail to spill");
+    assert_eq!(string, "¿höé wörld and a long tail to spill");
+    assert!(!string.is_inline());
+
+    assert_eq!(string.remove(0), '¿');
+    assert_eq!(string.remove(1), 'ö');
+    assert_eq!(string, "hé wörld and a long tail to spill");
+}
+
+#[test]
+fn retain_chars() {
+    let mut string = Str::from("a€b😀cé");
+    string.retain(|ch| ch.is_ascii());
+    assert_eq!(string, "abc");
+
+    let mut string = Str::from("x€y€z");
+    string.retain(|ch| ch != 'y');
+    assert_eq!(string, "x€€z");
+}
+
+#[test]
+fn retain_panic_keeps_utf8() {
+    let mut string = Str::from("a€b€c");
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        string.retain(|ch| {
+            assert_ne!(ch, 'b');
+            ch != '€'
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(string, "a");
+    assert!(std::str::from_utf8(string.as_bytes()).is_ok());
+}
+
+#[test]
+fn split_off() {
+    let mut string = Str::from("héllo wörld");
+    let tail = string.split_off(7);
+    assert_eq!(string, "héllo ");
+    assert_eq!(tail, "wörld");
+}
+
+#[test]
+#[should_panic]
+fn truncate_not_char_boundary() {
+    Str::from("é").truncate(1);
+}
+
+#[test]
+#[should_panic]
+fn insert_not_char_boundary() {
+    Str::from("é").insert(1, 'a');
+}
+
+#[test]
+#[should_panic]
+fn remove_not_char_boundary() {
+    Str::from("é").remove(1);
+}
+
+#[test]
+#[should_panic]
+fn split_off_not_char_boundary() {
+    let _ = Str::from("é").split_off(1);
+}
+
+#[test]
+fn drain_chars() {
+    let mut string = Str::from("héllo wörld");
+    let drained: String = string.drain(1..7).collect();
+    assert_eq!(drained, "éllo ");
+    assert_eq!(string, "hwörld");
+
+    let mut drain = string.drain(1..);
+    assert_eq!(drain.next_back(), Some('d'));
+    assert_eq!(drain.as_str(), "wörl");
+    drop(drain);
+    assert_eq!(string, "h");
+
+    string.drain(..);
+    assert!(string.is_empty());
+}
+
+#[test]
+fn drain_heap() {
+    let mut string = Str::from("a fairly long string that lives on the heap");
+    assert!(!string.is_inline());
+    assert_eq!(string.drain(2..9).count(), 7);
+    assert_eq!(string, "a long string that lives on the heap");
+}
+
+#[test]
+#[should_panic]
+fn drain_not_char_boundary() {
+    Str::from("aé").drain(..2);
+}
+
+#[test]
+fn replace_range() {
+    let mut string = Str::from("héllo wörld");
+    string.replace_range(1..3, "e");
+    assert_eq!(string, "hello wörld");
+    assert!(string.is_inline());
+
+    string.replace_range(6.., "to a string that does not fit inline");
+    assert_eq!(string, "hello to a string that does not fit inline");
+    assert!(!string.is_inline());
+
+    string.replace_range(..=8, "");
+    assert_eq!(string, "a string that does not fit inline");
+    string.replace_range(2..9, "");
+    assert_eq!(string, "a that does not fit inline");
+}
+
+#[test]
+#[should_panic]
+fn replace_range_not_char_boundary() {
+    Str::from("aé").replace_range(2.., "x");
+}
+
+#[test]
+#[should_panic]
+fn replace_range_out_of_bounds() {
+    Str::from("abc").replace_range(..4, "x");
+}
+
+#[test]
+fn from_utf8_shares_buffer() {
+    let bytes: Vector<u8> = "a string long enough to need the heap".bytes().collect();
+    let ptr = bytes.as_ptr();
+    let string = Str::from_utf8(bytes).unwrap();
+    assert_eq!(string, "a string long enough to need the heap");
+    assert_eq!(string.as_ptr(), ptr);
+
+    let bytes = string.into_bytes();
+    assert_eq!(bytes.as_ptr(), ptr);
+
+    let inline: Vector<u8> = Vector::from(*b"h\xc3\xa9");
+    let string = Str::from_utf8(inline).unwrap();
+    assert!(string.is_inline());
+    assert_eq!(string, "hé");
+}
+
+#[test]
+fn from_utf8_error_returns_buffer() {
+    let bytes: Vector<u8> = (0..200u8).collect();
+    let ptr = bytes.as_ptr();
+
+    let error = Str::from_utf8(bytes).unwrap_err();
+    assert_eq!(error.utf8_error().valid_up_to(), 128);
+    assert_eq!(error.as_bytes().len(), 200);
+    assert_eq!(
+        error.to_string(),
+        "invalid utf-8 sequence of 1 bytes from index 128"
+    );
+
+    let bytes = error.into_bytes();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(bytes.len(), 200);
+}
+
+#[test]
+fn from_utf8_lossy() {
+    let string = Str::from_utf8_lossy(b"caf\xc3\xa9 \xff\xfe ok");
+    assert_eq!(string, "café \u{FFFD}\u{FFFD} ok");
+
+    let string = Str::from_utf8_lossy(b"valid");
+    assert_eq!(string, "valid");
+    assert!(string.is_inline());
+}
+
+#[test]
+fn from_utf8_unchecked() {
+    let bytes: Vector<u8> = Vector::from(*b"abc");
+    let string = unsafe { Str::from_utf8_unchecked(bytes) };
+    assert_eq!(string, "abc");
+}
+
+#[test]
+fn write_into_str() {
+    use core::fmt::Write;
+
+    let mut string = Str::new();
+    let (number, ch) = (1, 'é');
+    write!(string, "{number}-{ch}").unwrap();
+    string.write_char('!').unwrap();
+    assert_eq!(string, "1-é!");
+    assert!(string.is_inline());
+
+    writeln!(string, " and {:>30}", "padding that spills").unwrap();
+    assert_eq!(string, "1-é! and            padding that spills\n");
+    assert!(!string.is_inline());
+}
+
+#[test]
+fn format_str_macro() {
+    let value = 42;
+    let string = demon_core::format_str!("value = {value:04}");
+    assert_eq!(string, "value = 0042");
+    assert!(string.is_inline());
+
+    let string = demon_core::format_str!("no placeholders but long enough to spill");
+    assert_eq!(string, "no placeholders but long enough to spill");
+    assert_eq!(string.capacity(), string.len());
+
+    let string = demon_core::format_str!("{:?}", [1, 2, 3].repeat(10));
+    assert_eq!(string, format!("{:?}", [1, 2, 3].repeat(10)));
+
+    let (first_name, last_name) = ("Al", "Bo");
+    let string = demon_core::format_str!("{first_name} and also {last_name}");
+    assert_eq!(string, "Al and also Bo");
+    assert!(string.is_inline());
+
+    let (a, b, c, d, e, f) = (1, 2, 3, 4, 5, 6);
+    let string = demon_core::format_str!("{a:>3}-{b:>3}-{c:>3}-{d:>3}-{e:>3}-{f:>3}");
+    assert_eq!(string, "  1-  2-  3-  4-  5-  6");
+    assert!(string.is_inline());
+
+    let string = demon_core::format_str!(
+        "{{value}} = {value}, followed by enough text to need the heap anyway"
+    );
+    assert_eq!(
+        string,
+        "{value} = 42, followed by enough text to need the heap anyway"
+    );
+    assert!(!string.is_inline());
+}
+
+#[test]
+fn to_str() {
+    use demon_core::ToStr;
+
+    assert_eq!(42.to_str(), "42");
+    assert_eq!('é'.to_str(), "é");
+    assert_eq!("borrowed".to_str(), "borrowed");
+    assert!(1.5f32.to_str().is_inline());
+}
+
+#[test]
+fn growth_policy() {
+    use demon_core::{Exact, FixedChunk};
+
+    let mut string: Str<INLINE_SIZE, Global, Exact> = Str::new_in(Global);
+    string.push_str("a string that is long enough to spill");
+    assert_eq!(string.capacity(), string.len());
+    string.push('!');
+    assert_eq!(string.capacity(), string.len());
+
+    let clone = string.clone();
+    assert_eq!(clone, string);
+    let mut clone = clone.with_growth_policy(FixedChunk(100));
+    assert_eq!(clone.growth_policy(), &FixedChunk(100));
+    clone.push('?');
+    assert!(clone.capacity().is_multiple_of(100));
+
+    let tail = clone.split_off(2);
+    assert_eq!(tail.growth_policy(), &FixedChunk(100));
+
+    let bytes: Vector<u8, INLINE_SIZE, Global, Exact> = Vector::from_heap_in(b"bytes", Global);
+    let string = Str::from_utf8(bytes).unwrap();
+    assert_eq!(string.growth_policy(), &Exact);
+    assert_eq!(string.into_bytes(), b"bytes");
+}
+
+#[test]
+fn nameable_drain() {
+    let mut string = Str::from("héllo");
+    let drain: demon_core::str::Drain<'_> = string.drain(1..3);
+    assert_eq!(drain.as_str(), "é");
+    drop(drain);
+    assert_eq!(string, "hllo");
+}
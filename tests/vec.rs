@@ -1,4 +1,10 @@
-use demon_core::Vector;
+#![allow(clippy::bool_assert_comparison, clippy::vec_init_then_push)]
+
+use std::rc::Rc;
+
+use demon_core::{
+    Exact, FixedChunk, Global, MinimumFirst, OneAndAHalf, TryReserveErrorKind, Vector, INLINE_SIZE,
+};
 
 #[test]
 fn get() {
@@ -267,27 +273,29 @@ fn into_iter() {
 fn drain() {
     let mut vec = Vector::<u32>::new();
     vec.extend_from_slice(&[0, 1]);
-    vec.drain()
+    vec.drain(..)
         .enumerate()
         .for_each(|(i, x)| assert_eq!(i as u32, x));
 
     let mut vec = Vector::<usize>::new();
     vec.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
-    vec.drain().enumerate().for_each(|(i, x)| assert_eq!(i, x));
+    vec.drain(..)
+        .enumerate()
+        .for_each(|(i, x)| assert_eq!(i, x));
 }
 
 #[test]
 fn drain_double_ended() {
     let mut vec = Vector::<u32>::new();
     vec.extend_from_slice(&[0, 1]);
-    vec.drain()
+    vec.drain(..)
         .enumerate()
         .rev()
         .for_each(|(i, x)| assert_eq!(i as u32, x));
 
     let mut vec = Vector::<usize>::new();
     vec.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
-    vec.drain()
+    vec.drain(..)
         .enumerate()
         .rev()
         .for_each(|(i, x)| assert_eq!(i, x));
@@ -350,6 +358,7 @@ struct AdtPartialEq<'a> {
     some_str: &'a str,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Eq)]
 struct AdtEq<'a> {
     some_str: &'a str,
@@ -476,3 +485,1318 @@ fn vector_str() {
 
     assert_eq!(vec, Vector::from(["x", "y"]));
 }
+
+#[test]
+fn custom_inline_size() {
+    let mut vec: Vector<u8, 40> = Vector::new_in(Global);
+    vec.extend(0..40);
+
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.capacity(), 40);
+    assert_eq!(vec.len(), 40);
+
+    vec.push(40);
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec.iter().copied().eq(0..41));
+}
+
+#[test]
+fn largest_inline_size_keeps_tags_apart() {
+    let mut vec: Vector<u8, 126> = Vector::new_in(Global);
+    vec.extend(0..126);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.len(), 126);
+
+    vec.push(126);
+    assert_eq!(vec.is_inline(), false);
+
+    // heap capacities with the top bit of their low byte set must not read as inline
+    for capacity in [128, 255, 384, 1 << 15] {
+        let mut vec: Vector<u8, 126> = Vector::with_capacity_in(capacity, Global);
+        vec.extend(0..127);
+        assert_eq!(vec.capacity(), capacity);
+        assert_eq!(vec.is_inline(), false);
+        assert!(vec.iter().copied().eq(0..127));
+    }
+}
+
+fn inline_capacity<T, const N: usize>() -> usize {
+    (N + 1 - core::mem::align_of::<T>()) / core::mem::size_of::<T>()
+}
+
+fn check_aligned_inline<T: Copy + PartialEq + core::fmt::Debug, const N: usize>(value: T) {
+    let mut vec: Vector<T, N> = Vector::new_in(Global);
+    let capacity = inline_capacity::<T, N>();
+    assert_eq!(vec.capacity(), capacity);
+
+    for _ in 0..capacity {
+        vec.push(value);
+        assert_eq!(vec.is_inline(), true);
+        assert_eq!(vec.as_ptr() as usize % core::mem::align_of::<T>(), 0);
+    }
+    assert_eq!(vec.len(), capacity);
+
+    vec.push(value);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.len(), capacity + 1);
+    assert!(vec.iter().all(|elem| *elem == value));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(align(16))]
+struct Align16(u8);
+
+#[test]
+fn aligned_inline() {
+    check_aligned_inline::<u16, INLINE_SIZE>(0xABCD);
+    check_aligned_inline::<u32, INLINE_SIZE>(0xDEADBEEF);
+    check_aligned_inline::<u64, INLINE_SIZE>(0xDEADBEEFCAFEF00D);
+    check_aligned_inline::<f64, INLINE_SIZE>(1.5);
+    check_aligned_inline::<Align16, INLINE_SIZE>(Align16(7));
+
+    check_aligned_inline::<u32, 35>(0xDEADBEEF);
+    check_aligned_inline::<Align16, 47>(Align16(7));
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn aligned_inline_capacity() {
+    assert_eq!(Vector::<u16>::new().capacity(), 11);
+    assert_eq!(Vector::<u32>::new().capacity(), 5);
+    assert_eq!(Vector::<u64>::new().capacity(), 2);
+    assert_eq!(Vector::<Align16>::new().capacity(), 0);
+    assert_eq!(Vector::<u32, 35>::new_in(Global).capacity(), 8);
+    assert_eq!(Vector::<Align16, 47>::new_in(Global).capacity(), 2);
+}
+
+fn grow_through_powers_of_two<T: Copy + PartialEq + core::fmt::Debug + TryFrom<usize>>() {
+    let mut vec = Vector::<T>::new();
+    let mut power = 1;
+
+    while power <= 8192 {
+        while vec.len() < power + 1 {
+            let value = T::try_from(vec.len() % 100).ok().unwrap();
+            vec.push(value);
+        }
+
+        assert!(vec.capacity() >= vec.len());
+        if vec.len() > Vector::<T>::new().capacity() {
+            assert_eq!(vec.is_inline(), false);
+        }
+        for (i, elem) in vec.iter().enumerate() {
+            assert_eq!(*elem, T::try_from(i % 100).ok().unwrap());
+        }
+
+        power *= 2;
+    }
+}
+
+#[test]
+fn grow_heap_capacity() {
+    grow_through_powers_of_two::<u8>();
+    grow_through_powers_of_two::<u16>();
+    grow_through_powers_of_two::<u32>();
+    grow_through_powers_of_two::<u64>();
+}
+
+#[test]
+fn grow_heap_capacity_from_empty_heap() {
+    let mut vec = Vector::<u8>::new_heap();
+    for i in 0..4096 {
+        vec.push((i % 256) as u8);
+        assert_eq!(vec.is_inline(), false);
+    }
+    assert!(vec.iter().enumerate().all(|(i, x)| *x == (i % 256) as u8));
+}
+
+#[test]
+fn growth_policy_exact() {
+    let mut vec: Vector<u8, INLINE_SIZE, Global, Exact> = Vector::new_in(Global);
+    for i in 0..64 {
+        vec.push(i);
+        assert_eq!(vec.capacity(), vec.len().max(INLINE_SIZE));
+    }
+
+    vec.extend_from_slice(&[0; 10]);
+    assert_eq!(vec.capacity(), 74);
+    vec.insert(vec.len(), 0xFF);
+    assert_eq!(vec.capacity(), 75);
+    vec.extend(0..5);
+    assert_eq!(vec.capacity(), 80);
+}
+
+#[test]
+fn growth_policy_fixed_chunk() {
+    let mut vec = Vector::<u8>::new().with_growth_policy(FixedChunk(16));
+    vec.extend_from_slice(&[1; INLINE_SIZE]);
+    assert_eq!(vec.is_inline(), true);
+
+    vec.push(2);
+    assert_eq!(vec.capacity(), (INLINE_SIZE + 1).div_ceil(16) * 16);
+
+    vec.extend_from_slice(&[3; 100]);
+    assert_eq!(vec.capacity() % 16, 0);
+    assert_eq!(vec.capacity() - vec.len() < 16, true);
+    assert_eq!(vec.len(), INLINE_SIZE + 101);
+}
+
+#[test]
+fn growth_policy_one_and_a_half() {
+    let mut vec: Vector<u32, INLINE_SIZE, Global, OneAndAHalf> = Vector::new_in(Global);
+    let inline = vec.capacity();
+    vec.extend(0..inline as u32 + 1);
+    assert_eq!(vec.is_inline(), false);
+
+    let mut expected = (inline + inline / 2).max(inline + 1);
+    assert_eq!(vec.capacity(), expected);
+
+    for i in 0..1000 {
+        if vec.len() == vec.capacity() {
+            expected += expected / 2;
+        }
+        vec.push(i);
+        assert_eq!(vec.capacity(), expected);
+    }
+}
+
+#[test]
+fn growth_policy_minimum_first() {
+    let mut vec = Vector::<u8>::new_heap().with_growth_policy(MinimumFirst {
+        min: 64,
+        then: Exact,
+    });
+    vec.push(1);
+    assert_eq!(vec.capacity(), 64);
+
+    vec.extend_from_slice(&[2; 63]);
+    assert_eq!(vec.capacity(), 64);
+
+    vec.push(3);
+    assert_eq!(vec.capacity(), 65);
+    assert_eq!(vec.growth_policy().min, 64);
+}
+
+#[test]
+fn growth_policy_switch_keeps_contents() {
+    let vec = Vector::<u16>::from_heap(&[1, 2, 3]);
+    let cap = vec.capacity();
+    let mut vec = vec.with_growth_policy(Exact);
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.capacity(), cap);
+
+    vec.extend(4..=cap as u16 + 1);
+    assert_eq!(vec.capacity(), cap + 1);
+    assert!(vec.iter().copied().eq(1..=cap as u16 + 1));
+}
+
+#[test]
+fn try_reserve() {
+    let mut vec = Vector::<u32>::new();
+    assert_eq!(vec.try_reserve(2), Ok(()));
+    assert_eq!(vec.is_inline(), true);
+
+    assert_eq!(vec.try_reserve(100), Ok(()));
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec.capacity() >= 100);
+
+    let mut vec = Vector::<u32>::new_heap();
+    assert_eq!(vec.try_reserve_exact(7), Ok(()));
+    assert_eq!(vec.capacity(), 7);
+    vec.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(vec.try_reserve_exact(4), Ok(()));
+    assert_eq!(vec.capacity(), 7);
+    assert_eq!(vec.try_reserve_exact(5), Ok(()));
+    assert_eq!(vec.capacity(), 8);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut vec = Vector::<u8>::new();
+    vec.extend(0..100);
+    assert_eq!(vec.is_inline(), false);
+
+    while vec.len() > 50 {
+        vec.pop();
+    }
+    vec.shrink_to_fit();
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.capacity(), 50);
+    assert!(vec.iter().copied().eq(0..50));
+
+    while vec.len() > INLINE_SIZE {
+        vec.pop();
+    }
+    vec.shrink_to_fit();
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.capacity(), INLINE_SIZE);
+    assert!(vec.iter().copied().eq(0..INLINE_SIZE as u8));
+
+    vec.shrink_to_fit();
+    assert_eq!(vec.is_inline(), true);
+}
+
+#[test]
+fn shrink_to() {
+    let mut vec = Vector::<u32>::new();
+    vec.extend(0..200);
+    let cap = vec.capacity();
+
+    vec.shrink_to(cap + 10);
+    assert_eq!(vec.capacity(), cap);
+
+    vec.shrink_to(150);
+    assert_eq!(vec.capacity(), 200);
+
+    while vec.len() > 10 {
+        vec.pop();
+    }
+    vec.shrink_to(120);
+    assert_eq!(vec.capacity(), 120);
+    assert!(vec.iter().copied().eq(0..10));
+
+    while vec.len() > 1 {
+        vec.pop();
+    }
+    vec.shrink_to(0);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec, [0]);
+}
+
+#[test]
+fn try_inline() {
+    let mut vec = Vector::<u16>::from_heap(&[1, 2, 3]);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.try_inline(), true);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.try_inline(), true);
+
+    let mut vec = Vector::<u16>::new();
+    vec.extend(0..100);
+    let cap = vec.capacity();
+    assert_eq!(vec.try_inline(), false);
+    assert_eq!(vec.capacity(), cap);
+    assert!(vec.iter().copied().eq(0..100));
+
+    let mut vec = Vector::<String, 64>::new_heap_in(Global);
+    vec.push("a".into());
+    vec.push("b".into());
+    assert_eq!(vec.try_inline(), true);
+    assert_eq!(vec, ["a", "b"]);
+    vec.push("c".into());
+    assert_eq!(vec, ["a", "b", "c"]);
+}
+
+#[test]
+fn try_reserve_capacity_overflow() {
+    let mut vec = Vector::<u32>::new();
+    vec.push(1);
+
+    let err = vec.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+
+    let err = vec.try_reserve_exact(usize::MAX / 2).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec, &[1]);
+
+    let mut vec = Vector::<u64>::from_heap(&[1, 2, 3]);
+    let err = vec.try_reserve(isize::MAX as usize / 8).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+    assert_eq!(vec, &[1, 2, 3]);
+}
+
+#[test]
+fn try_push_insert_extend() {
+    let mut vec = Vector::<u8>::new();
+    assert_eq!(vec.try_extend_from_slice(&[0, 1, 3]), Ok(()));
+    assert_eq!(vec.try_insert(2, 2), Ok(()));
+    assert_eq!(vec.try_push(4), Ok(()));
+    assert_eq!(vec, &[0, 1, 2, 3, 4]);
+
+    let long: Vec<u8> = (5..64).collect();
+    assert_eq!(vec.try_extend_from_slice(&long), Ok(()));
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec.iter().copied().eq(0..64));
+}
+
+#[test]
+#[should_panic(expected = "index is out of range")]
+fn try_insert_out_of_range() {
+    let mut vec = Vector::<u8>::new();
+    let _ = vec.try_insert(1, 0);
+}
+
+#[test]
+fn raw_parts_round_trip() {
+    let vec: Vector<u32> = Vector::from([1, 2, 3]);
+    assert_eq!(vec.is_inline(), true);
+
+    let (ptr, len, cap) = vec.into_raw_parts();
+    assert_eq!(len, 3);
+    assert_eq!(cap, 3);
+
+    let mut vec = unsafe { Vector::<u32>::from_raw_parts(ptr, len, cap) };
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec, [1, 2, 3]);
+    vec.push(4);
+    assert_eq!(vec, [1, 2, 3, 4]);
+
+    let (ptr, len, cap) = Vector::<u32>::new().into_raw_parts();
+    assert_eq!((len, cap), (0, 0));
+    let mut vec = unsafe { Vector::<u32>::from_raw_parts(ptr, len, cap) };
+    vec.push(5);
+    assert_eq!(vec, [5]);
+}
+
+#[test]
+fn raw_parts_from_std_vec() {
+    let std_vec = core::mem::ManuallyDrop::new(vec![String::from("a"), String::from("b")]);
+    let (ptr, len, cap) = (
+        std_vec.as_ptr() as *mut String,
+        std_vec.len(),
+        std_vec.capacity(),
+    );
+
+    let mut vec = unsafe { Vector::<String>::from_raw_parts(ptr, len, cap) };
+    assert_eq!(vec.capacity(), cap);
+    vec.push(String::from("c"));
+    assert_eq!(vec, ["a", "b", "c"]);
+
+    let (ptr, len, cap) = vec.into_raw_parts();
+    let std_vec = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(std_vec, ["a", "b", "c"]);
+}
+
+#[test]
+fn leak() {
+    let vec: Vector<u8> = Vector::from([1, 2, 3]);
+    let leaked: &'static mut [u8] = vec.leak();
+    leaked[0] = 10;
+    assert_eq!(leaked, [10, 2, 3]);
+
+    let mut vec: Vector<u16> = Vector::new();
+    vec.extend(0..100);
+    let leaked = vec.leak();
+    assert!(leaked.iter().copied().eq(0..100));
+
+    let leaked = Vector::<u64>::new().leak();
+    assert_eq!(leaked.len(), 0);
+}
+
+#[test]
+fn from_std_vec_adopts_allocation() {
+    let std_vec: Vec<u32> = (0..100).collect();
+    let ptr = std_vec.as_ptr();
+    let cap = std_vec.capacity();
+
+    let vec = Vector::<u32>::from(std_vec);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), cap);
+    assert!(vec.iter().copied().eq(0..100));
+
+    let std_vec = Vec::from(vec);
+    assert_eq!(std_vec.as_ptr(), ptr);
+    assert_eq!(std_vec.capacity(), cap);
+    assert!(std_vec.into_iter().eq(0..100));
+}
+
+#[test]
+fn from_std_vec_short_goes_inline() {
+    let std_vec: Vec<Box<str>> = Vec::from([Box::from("a")]);
+    let vec = Vector::from(std_vec);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec, [Box::from("a")]);
+
+    let vec = Vector::<u8>::from(Vec::with_capacity(100));
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.is_empty(), true);
+
+    let std_vec = Vec::from(Vector::<u8>::from([1, 2, 3]));
+    assert_eq!(std_vec, [1, 2, 3]);
+    assert_eq!(std_vec.capacity(), 3);
+}
+
+#[test]
+fn boxed_slice() {
+    let boxed: Box<[u64]> = (0..50).collect();
+    let ptr = boxed.as_ptr();
+
+    let vec = Vector::<u64>::from(boxed);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 50);
+
+    let boxed = vec.into_boxed_slice();
+    assert_eq!(boxed.as_ptr(), ptr);
+    assert!(boxed.iter().copied().eq(0..50));
+
+    let mut vec = Vector::<u64>::with_capacity_in(100, Global);
+    vec.extend(0..60);
+    assert_eq!(vec.into_boxed_slice().len(), 60);
+
+    let boxed = Vector::<u8>::from([7, 8]).into_boxed_slice();
+    assert_eq!(&*boxed, [7, 8]);
+    assert_eq!(Vector::<u8>::from(boxed).is_inline(), true);
+}
+
+#[test]
+fn with_capacity() {
+    let vec = Vector::<u8>::with_capacity(INLINE_SIZE);
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.capacity(), INLINE_SIZE);
+
+    let vec = Vector::<u8>::with_capacity(INLINE_SIZE + 1);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.capacity(), INLINE_SIZE + 1);
+
+    let mut vec = Vector::<u32>::with_capacity(1000);
+    let ptr = vec.as_ptr();
+    vec.extend(0..1000);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 1000);
+}
+
+#[test]
+fn reserve() {
+    let mut vec = Vector::<u8>::new();
+    vec.reserve(INLINE_SIZE);
+    assert_eq!(vec.is_inline(), true);
+
+    vec.reserve(INLINE_SIZE + 1);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.capacity(), INLINE_SIZE * 2);
+
+    vec.extend(0..INLINE_SIZE as u8 * 2);
+    vec.reserve(1);
+    assert_eq!(vec.capacity(), INLINE_SIZE * 4);
+
+    vec.reserve(1000);
+    assert_eq!(vec.capacity(), INLINE_SIZE * 2 + 1000);
+
+    let cap = vec.capacity();
+    vec.reserve(cap - vec.len());
+    assert_eq!(vec.capacity(), cap);
+}
+
+#[test]
+fn reserve_exact() {
+    let mut vec = Vector::<u16>::new();
+    vec.reserve_exact(100);
+    assert_eq!(vec.capacity(), 100);
+
+    vec.extend(0..100);
+    vec.reserve_exact(1);
+    assert_eq!(vec.capacity(), 101);
+
+    vec.reserve_exact(0);
+    assert_eq!(vec.capacity(), 101);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn reserve_overflow() {
+    let mut vec = Vector::<u64>::new();
+    vec.push(1);
+    vec.reserve(usize::MAX);
+}
+
+#[test]
+fn truncate_drops_tail() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(10, rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 11);
+
+    vec.truncate(4);
+    assert_eq!(vec.len(), 4);
+    assert_eq!(Rc::strong_count(&rc), 5);
+    let cap = vec.capacity();
+    assert_eq!(vec.is_inline(), false);
+
+    vec.truncate(10);
+    assert_eq!(vec.len(), 4);
+
+    vec.clear();
+    assert_eq!(vec.is_empty(), true);
+    assert_eq!(vec.capacity(), cap);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn truncate_inline() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4]);
+    vec.truncate(2);
+    assert_eq!(vec, [1, 2]);
+    vec.clear();
+    assert_eq!(vec.is_inline(), true);
+    assert_eq!(vec.is_empty(), true);
+}
+
+#[test]
+fn resize() {
+    let mut vec: Vector<u16> = Vector::new();
+    vec.resize(3, 7);
+    assert_eq!(vec, [7, 7, 7]);
+    assert_eq!(vec.is_inline(), true);
+
+    vec.resize(100, 9);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.len(), 100);
+    assert!(vec[3..].iter().all(|x| *x == 9));
+
+    vec.resize(2, 0);
+    assert_eq!(vec, [7, 7]);
+
+    vec.resize(2, 0);
+    assert_eq!(vec, [7, 7]);
+}
+
+#[test]
+fn resize_moves_last_value() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(5, rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 6);
+
+    vec.resize(0, rc.clone());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn resize_with() {
+    let mut count = 0;
+    let mut vec: Vector<u32> = Vector::new();
+    vec.resize_with(40, || {
+        count += 1;
+        count
+    });
+    assert!(vec.iter().copied().eq(1..=40));
+
+    vec.resize_with(10, || unreachable!());
+    assert!(vec.iter().copied().eq(1..=10));
+}
+
+#[test]
+fn fill_drops_old_elements() {
+    let old = Rc::new(());
+    let new = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(30, old.clone());
+    assert_eq!(Rc::strong_count(&old), 31);
+
+    vec.fill(new.clone());
+    assert_eq!(Rc::strong_count(&old), 1);
+    assert_eq!(Rc::strong_count(&new), 31);
+
+    vec.truncate(3);
+    vec.fill_with(|| old.clone());
+    assert_eq!(Rc::strong_count(&old), 4);
+    assert_eq!(Rc::strong_count(&new), 1);
+
+    let mut empty: Vector<Rc<()>> = Vector::new();
+    empty.fill(new.clone());
+    assert_eq!(Rc::strong_count(&new), 1);
+}
+
+#[test]
+fn fill() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3]);
+    vec.fill(0);
+    assert_eq!(vec, [0, 0, 0]);
+
+    let mut next = 0;
+    vec.resize(50, 0);
+    vec.fill_with(|| {
+        next += 1;
+        next
+    });
+    assert!(vec.iter().copied().eq(1..=50));
+}
+
+#[test]
+fn drain_range() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3, 4, 5]);
+    assert!(vec.drain(1..3).eq([1, 2]));
+    assert_eq!(vec, [0, 3, 4, 5]);
+    assert_eq!(vec.is_inline(), true);
+
+    assert!(vec.drain(2..).eq([4, 5]));
+    assert_eq!(vec, [0, 3]);
+
+    assert!(vec.drain(..=0).eq([0]));
+    assert_eq!(vec, [3]);
+
+    assert_eq!(vec.drain(1..1).count(), 0);
+    assert_eq!(vec, [3]);
+
+    let mut vec: Vector<u32> = (0..100).collect();
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec.drain(10..90).rev().eq((10..90).rev()));
+    assert!(vec.iter().copied().eq((0..10).chain(90..100)));
+}
+
+#[test]
+fn drain_partially_consumed() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(50, rc.clone());
+
+    let mut drain = vec.drain(5..45);
+    assert_eq!(drain.as_slice().len(), 40);
+    drop(drain.next());
+    drop(drain.next_back());
+    assert_eq!(drain.len(), 38);
+    drop(drain);
+
+    assert_eq!(vec.len(), 10);
+    assert_eq!(Rc::strong_count(&rc), 11);
+}
+
+#[test]
+fn drain_forget_leaks_tail() {
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.extend(["a", "b", "c"].map(String::from));
+
+    core::mem::forget(vec.drain(1..2));
+    assert_eq!(vec, ["a"]);
+    vec.push(String::from("d"));
+    assert_eq!(vec, ["a", "d"]);
+}
+
+#[test]
+fn drain_panicking_drop_keeps_tail() {
+    struct PanicOnDrop(bool);
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0 {
+                panic!("dropped");
+            }
+        }
+    }
+
+    let mut vec: Vector<PanicOnDrop> = Vector::new();
+    vec.extend([false, true, false, false].map(PanicOnDrop));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        drop(vec.drain(..2));
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "range end index 5 out of range for slice of length 4")]
+fn drain_out_of_range() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3]);
+    vec.drain(2..5);
+}
+
+#[test]
+#[should_panic(expected = "slice index starts at 3 but ends at 2")]
+fn drain_decreasing_range() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3]);
+    #[allow(clippy::reversed_empty_ranges)]
+    vec.drain(3..2);
+}
+
+#[test]
+fn splice() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3, 4]);
+    let removed: Vec<u8> = vec.splice(1..3, [10, 11, 12]).collect();
+    assert_eq!(removed, [1, 2]);
+    assert_eq!(vec, [0, 10, 11, 12, 3, 4]);
+
+    vec.splice(..2, []);
+    assert_eq!(vec, [11, 12, 3, 4]);
+
+    vec.splice(4.., [5, 6]);
+    assert_eq!(vec, [11, 12, 3, 4, 5, 6]);
+    assert_eq!(vec.is_inline(), true);
+}
+
+#[test]
+fn splice_crosses_to_heap() {
+    let mut vec: Vector<u16> = Vector::from([0, 1, 2, 3]);
+    vec.splice(2..3, 100..140);
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec
+        .iter()
+        .copied()
+        .eq([0, 1].into_iter().chain(100..140).chain([3])));
+
+    // no size hint forces the replacement to be collected first
+    let mut vec: Vector<u16> = Vector::from([0, 1, 2, 3]);
+    vec.splice(1..1, (200..240).filter(|_| true));
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec
+        .iter()
+        .copied()
+        .eq([0].into_iter().chain(200..240).chain(1..4)));
+}
+
+#[test]
+fn splice_unconsumed_drops() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(10, rc.clone());
+
+    let replacement = Rc::new(());
+    drop(vec.splice(2..8, [replacement.clone(), replacement.clone()]));
+    assert_eq!(vec.len(), 6);
+    assert_eq!(Rc::strong_count(&rc), 5);
+    assert_eq!(Rc::strong_count(&replacement), 3);
+}
+
+#[test]
+fn splice_panicking_iterator() {
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.extend(["a", "b", "c"].map(String::from));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let replacement = (0..5).map(|i| {
+            if i == 2 {
+                panic!("replacement");
+            }
+            i.to_string()
+        });
+        drop(vec.splice(1..2, replacement));
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec, ["a", "0", "1", "c"]);
+}
+
+#[test]
+fn extract_if() {
+    let mut vec: Vector<u32> = (0..100).collect();
+    let evens: Vec<u32> = vec.extract_if(.., |x| *x % 2 == 0).collect();
+    assert!(evens.into_iter().eq((0..100).step_by(2)));
+    assert!(vec.iter().copied().eq((1..100).step_by(2)));
+
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4, 5, 6]);
+    let extracted: Vec<u8> = vec
+        .extract_if(1..5, |x| {
+            *x *= 10;
+            *x > 25
+        })
+        .collect();
+    assert_eq!(extracted, [30, 40, 50]);
+    assert_eq!(vec, [1, 20, 6]);
+}
+
+#[test]
+fn extract_if_dropped_early() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4, 5, 6]);
+    let mut iter = vec.extract_if(.., |x| *x % 2 == 0);
+    assert_eq!(iter.next(), Some(2));
+    drop(iter);
+    assert_eq!(vec, [1, 3, 4, 5, 6]);
+}
+
+#[test]
+fn extract_if_panicking_filter() {
+    let rc = Rc::new(());
+    let mut vec: Vector<(u32, Rc<()>)> = (0..40).map(|i| (i, rc.clone())).collect();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let filter = |x: &mut (u32, Rc<()>)| {
+            if x.0 == 20 {
+                panic!("filter");
+            }
+            x.0.is_multiple_of(3)
+        };
+        vec.extract_if(.., filter).for_each(drop);
+    }));
+    assert!(result.is_err());
+
+    assert_eq!(vec.len(), 40 - 7);
+    assert!(vec
+        .iter()
+        .map(|x| x.0)
+        .eq((0..40).filter(|i: &u32| *i >= 20 || !i.is_multiple_of(3))));
+    assert_eq!(Rc::strong_count(&rc), 34);
+}
+
+#[test]
+fn retain() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4, 5, 6]);
+    vec.retain(|x| *x % 2 == 1);
+    assert_eq!(vec, [1, 3, 5]);
+    assert_eq!(vec.is_inline(), true);
+
+    let mut vec: Vector<u32> = (0..100).collect();
+    let ptr = vec.as_ptr();
+    vec.retain(|x| *x >= 90);
+    assert!(vec.iter().copied().eq(90..100));
+    assert_eq!(vec.as_ptr(), ptr);
+}
+
+#[test]
+fn retain_mut() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4]);
+    vec.retain_mut(|x| {
+        *x *= 3;
+        *x % 2 == 0
+    });
+    assert_eq!(vec, [6, 12]);
+}
+
+#[test]
+fn retain_drops_once() {
+    let rc = Rc::new(());
+    let mut vec: Vector<(u32, Rc<()>)> = (0..30).map(|i| (i, rc.clone())).collect();
+    vec.retain(|x| x.0 < 10);
+    assert_eq!(vec.len(), 10);
+    assert_eq!(Rc::strong_count(&rc), 11);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.retain(|x| {
+            if x.0 == 5 {
+                panic!("keep");
+            }
+            x.0 % 2 == 0
+        });
+    }));
+    assert!(result.is_err());
+    assert!(vec.iter().map(|x| x.0).eq([0, 2, 4, 5, 6, 7, 8, 9]));
+    assert_eq!(Rc::strong_count(&rc), 9);
+}
+
+#[test]
+fn dedup() {
+    let mut vec: Vector<u8> = Vector::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    vec.dedup();
+    assert_eq!(vec, [1, 2, 3, 1, 4]);
+
+    let mut vec: Vector<u8> = Vector::new();
+    vec.dedup();
+    assert_eq!(vec.is_empty(), true);
+
+    let mut vec: Vector<u32> = (0..200).map(|i| i / 10).collect();
+    vec.dedup();
+    assert!(vec.iter().copied().eq(0..20));
+}
+
+#[test]
+fn dedup_by_and_key() {
+    let mut vec: Vector<&str> = Vector::from(["a", "A", "b", "B", "b", "c"]);
+    vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    assert_eq!(vec, ["a", "b", "c"]);
+
+    let mut vec: Vector<u32> = Vector::from([10, 11, 20, 21, 22, 30]);
+    vec.dedup_by_key(|x| *x / 10);
+    assert_eq!(vec, [10, 20, 30]);
+}
+
+#[test]
+fn dedup_panicking_comparison() {
+    let rc = Rc::new(());
+    let mut vec: Vector<(u32, Rc<()>)> = [0, 0, 1, 1, 2, 2, 3, 3]
+        .into_iter()
+        .map(|i| (i, rc.clone()))
+        .collect();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.dedup_by(|a, b| {
+            if a.0 == 2 {
+                panic!("compare");
+            }
+            a.0 == b.0
+        });
+    }));
+    assert!(result.is_err());
+    assert!(vec.iter().map(|x| x.0).eq([0, 1, 2, 2, 3, 3]));
+    assert_eq!(Rc::strong_count(&rc), 7);
+}
+
+#[test]
+fn split_off() {
+    let mut vec: Vector<u32> = (0..100).collect();
+    let cap = vec.capacity();
+
+    let tail = vec.split_off(97);
+    assert_eq!(tail, [97, 98, 99]);
+    assert_eq!(tail.is_inline(), true);
+    assert_eq!(vec.len(), 97);
+    assert_eq!(vec.capacity(), cap);
+
+    let tail = vec.split_off(10);
+    assert_eq!(tail.is_inline(), false);
+    assert!(tail.iter().copied().eq(10..97));
+    assert!(vec.iter().copied().eq(0..10));
+
+    let tail = vec.split_off(10);
+    assert_eq!(tail.is_empty(), true);
+    let all = vec.split_off(0);
+    assert_eq!(vec.is_empty(), true);
+    assert!(all.iter().copied().eq(0..10));
+}
+
+#[test]
+#[should_panic(expected = "`at` split index (is 4) should be <= len (is 3)")]
+fn split_off_out_of_range() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3]);
+    vec.split_off(4);
+}
+
+#[test]
+fn append() {
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.push(String::from("a"));
+    let mut other: Vector<String, 64> = Vector::new_in(Global);
+    other.extend(["b", "c", "d"].map(String::from));
+
+    vec.append(&mut other);
+    assert_eq!(vec, ["a", "b", "c", "d"]);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(other.is_empty(), true);
+
+    let mut other: Vector<String, 64> = Vector::new_in(Global);
+    vec.append(&mut other);
+    assert_eq!(vec.len(), 4);
+}
+
+#[test]
+fn append_heap() {
+    let mut vec: Vector<u32> = (0..50).collect();
+    let mut other: Vector<u32> = (50..150).collect();
+    let other_cap = other.capacity();
+
+    vec.append(&mut other);
+    assert!(vec.iter().copied().eq(0..150));
+    assert_eq!(other.len(), 0);
+    assert_eq!(other.capacity(), other_cap);
+}
+
+#[test]
+fn extend_from_within() {
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.push(String::from("a"));
+    vec.push(String::from("b"));
+
+    vec.extend_from_within(..);
+    assert_eq!(vec, ["a", "b", "a", "b"]);
+
+    vec.extend_from_within(1..2);
+    assert_eq!(vec, ["a", "b", "a", "b", "b"]);
+
+    let mut vec: Vector<u8> = (0..20).collect();
+    vec.extend_from_within(5..);
+    assert!(vec.iter().copied().eq((0..20).chain(5..20)));
+}
+
+#[test]
+fn remove_returns_element_at_index() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3, 4]);
+    assert_eq!(vec.remove(2), 2);
+    assert_eq!(vec, [0, 1, 3, 4]);
+    assert_eq!(vec.remove(3), 4);
+    assert_eq!(vec.remove(0), 0);
+    assert_eq!(vec, [1, 3]);
+
+    let mut vec: Vector<u32> = (0..100).collect();
+    assert_eq!(vec.remove(50), 50);
+    assert!(vec.iter().copied().eq((0..50).chain(51..100)));
+}
+
+#[test]
+#[should_panic(expected = "index out of range")]
+fn remove_at_len() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2]);
+    vec.remove(3);
+}
+
+#[test]
+fn insert_shifts_overlapping_tail() {
+    let mut vec: Vector<u8> = Vector::from([1, 2, 3, 4]);
+    vec.insert(0, 0);
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+    vec.insert(2, 9);
+    assert_eq!(vec, [0, 1, 9, 2, 3, 4]);
+
+    let mut vec: Vector<u32> = Vector::new();
+    for i in 0..100 {
+        vec.insert(0, i);
+    }
+    assert!(vec.iter().copied().eq((0..100).rev()));
+}
+
+#[test]
+#[should_panic(expected = "index is out of range")]
+fn insert_past_len() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2]);
+    vec.insert(4, 0);
+}
+
+#[test]
+fn swap_remove() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2, 3, 4]);
+    assert_eq!(vec.swap_remove(1), 1);
+    assert_eq!(vec, [0, 4, 2, 3]);
+    assert_eq!(vec.swap_remove(3), 3);
+    assert_eq!(vec, [0, 4, 2]);
+
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.push(String::from("only"));
+    assert_eq!(vec.swap_remove(0), "only");
+    assert_eq!(vec.is_empty(), true);
+}
+
+#[test]
+#[should_panic(expected = "index out of range")]
+fn swap_remove_out_of_range() {
+    let mut vec: Vector<u8> = Vector::new();
+    vec.swap_remove(0);
+}
+
+#[test]
+fn insert_from_slice() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 5]);
+    vec.insert_from_slice(2, &[2, 3, 4]);
+    assert_eq!(vec, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(vec.is_inline(), true);
+
+    let data: Vec<u8> = (100..150).collect();
+    vec.insert_from_slice(0, &data);
+    assert_eq!(vec.is_inline(), false);
+    assert!(vec.iter().copied().eq((100..150).chain(0..6)));
+
+    vec.insert_from_slice(vec.len(), &[7]);
+    assert_eq!(vec.last(), Some(&7));
+}
+
+#[test]
+fn insert_many() {
+    let mut vec: Vector<String, 64> = Vector::new_in(Global);
+    vec.extend(["a", "e"].map(String::from));
+    vec.insert_many(1, ["b", "c", "d"].map(String::from));
+    assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+
+    let mut vec: Vector<u32> = Vector::from([0, 1000]);
+    vec.insert_many(1, (1..1000).filter(|_| true));
+    assert!(vec.iter().copied().eq(0..=1000));
+}
+
+#[test]
+#[should_panic(expected = "index is out of range")]
+fn insert_many_past_len() {
+    let mut vec: Vector<u8> = Vector::from([0, 1, 2]);
+    vec.insert_many(4, [0]);
+}
+
+/// Writes up to `buf.len()` bytes like `read(2)`, returning how many were written.
+fn fake_read(buf: &mut [core::mem::MaybeUninit<u8>], data: &[u8]) -> usize {
+    let count = buf.len().min(data.len());
+    for (slot, byte) in buf.iter_mut().zip(data) {
+        slot.write(*byte);
+    }
+    count
+}
+
+#[test]
+fn spare_capacity_inline() {
+    let mut vec: Vector<u8> = Vector::from([1, 2]);
+    let spare = vec.spare_capacity_mut();
+    assert_eq!(spare.len(), INLINE_SIZE - 2);
+
+    let read = fake_read(spare, &[3, 4, 5]);
+    unsafe { vec.set_len(vec.len() + read) };
+    assert_eq!(vec, [1, 2, 3, 4, 5]);
+    assert_eq!(vec.is_inline(), true);
+}
+
+#[test]
+fn spare_capacity_heap() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut vec: Vector<u8> = Vector::new();
+    let mut rest = data.as_slice();
+
+    while !rest.is_empty() {
+        vec.reserve(64);
+        let read = fake_read(vec.spare_capacity_mut(), &rest[..rest.len().min(64)]);
+        unsafe { vec.set_len(vec.len() + read) };
+        rest = &rest[read..];
+    }
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec, data.as_slice());
+    assert_eq!(vec.spare_capacity_mut().len(), vec.capacity() - vec.len());
+}
+
+#[test]
+fn set_len_shortens_without_dropping() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(3, rc.clone());
+
+    unsafe { vec.set_len(1) };
+    assert_eq!(Rc::strong_count(&rc), 4);
+    unsafe { vec.set_len(3) };
+    drop(vec);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn into_iter_len_and_size_hint() {
+    let vec: Vector<u32> = (0..100).collect();
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 100);
+
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), 98);
+    assert_eq!(iter.size_hint(), (98, Some(98)));
+
+    let rest: Vec<u32> = iter.collect();
+    assert_eq!(rest.capacity(), 98);
+    assert!(rest.into_iter().eq(1..99));
+}
+
+#[test]
+fn into_iter_fused() {
+    let vec: Vector<u8> = Vector::from([1]);
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.len(), 0);
+}
+
+#[test]
+fn into_iter_as_slice() {
+    let vec: Vector<u8> = Vector::from([1, 2, 3, 4]);
+    let mut iter = vec.into_iter();
+    iter.next();
+    assert_eq!(iter.as_slice(), [2, 3, 4]);
+
+    iter.as_mut_slice()[0] = 20;
+    assert_eq!(iter.next(), Some(20));
+    assert_eq!(format!("{iter:?}"), "IntoIter([3, 4])");
+}
+
+#[test]
+fn into_iter_clone() {
+    let vec: Vector<String, 64> = [String::from("a"), String::from("b")].into_iter().collect();
+    let mut iter = vec.into_iter();
+    let cl = iter.clone();
+    assert_eq!(iter.next().as_deref(), Some("a"));
+    let cl2 = iter.clone();
+
+    assert_eq!(cl.collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(cl2.collect::<Vec<_>>(), ["b"]);
+    assert_eq!(iter.collect::<Vec<_>>(), ["b"]);
+}
+
+#[test]
+fn into_iter_drops_remaining_once() {
+    let rc = Rc::new(());
+    let mut vec: Vector<Rc<()>> = Vector::new();
+    vec.resize(40, rc.clone());
+
+    let mut iter = vec.into_iter();
+    for _ in 0..15 {
+        drop(iter.next());
+    }
+    assert_eq!(Rc::strong_count(&rc), 26);
+    drop(iter);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn hash_matches_slice() {
+    use std::hash::BuildHasher;
+
+    let state = std::collections::hash_map::RandomState::new();
+    let inline: Vector<u8> = Vector::from([1, 2, 3]);
+    let heap: Vector<u8> = (0..100).collect();
+    assert!(!heap.is_inline());
+
+    assert_eq!(state.hash_one(&inline), state.hash_one(&[1u8, 2, 3][..]));
+    assert_eq!(
+        state.hash_one(&heap),
+        state.hash_one(&*(0..100).collect::<Vec<u8>>())
+    );
+}
+
+#[test]
+fn lookup_by_slice() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(Vector::<u32>::from([1, 2]), "short");
+    map.insert((0..50).collect::<Vector<u32>>(), "long");
+    assert_eq!(map.get(&[1u32, 2][..]), Some(&"short"));
+    assert_eq!(map.get(&*(0..50).collect::<Vec<u32>>()), Some(&"long"));
+
+    let set: std::collections::BTreeSet<Vector<u32>> = [Vector::from([3]), Vector::from([1, 2])]
+        .into_iter()
+        .collect();
+    assert!(set.contains(&[3u32][..]));
+}
+
+#[test]
+fn ordering() {
+    let a: Vector<u32> = Vector::from([1, 2, 3]);
+    let b: Vector<u32> = Vector::from([1, 3]);
+    let c: Vector<u32, 64> = [1, 2, 3].into_iter().collect();
+
+    assert!(a < b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Less);
+    assert_eq!(a.partial_cmp(&c), Some(std::cmp::Ordering::Equal));
+
+    let mut vecs = vec![b.clone(), a.clone(), Vector::new()];
+    vecs.sort();
+    assert_eq!(vecs, [Vector::new(), a, b]);
+}
+
+#[test]
+fn default_and_ref_iteration() {
+    let mut vec: Vector<u32, 8, Global, Exact> = Default::default();
+    assert!(vec.is_empty());
+    vec.extend([1, 2, 3]);
+
+    for x in &mut vec {
+        *x *= 2;
+    }
+    let mut sum = 0;
+    for x in &vec {
+        sum += *x;
+    }
+    assert_eq!(sum, 12);
+    assert_eq!(vec.get(1), Some(&4));
+    assert_eq!(AsMut::<[u32]>::as_mut(&mut vec), [2, 4, 6]);
+}
+
+#[test]
+fn send_sync_with_growth_policy() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let vec: Vector<String, 64, Global, MinimumFirst<OneAndAHalf>> = Vector::new_in(Global);
+    assert_send_sync(&vec);
+    assert_send_sync(&vec.into_iter());
+
+    let vec = Vector::<u8>::new().with_growth_policy(FixedChunk(8));
+    std::thread::spawn(move || assert_eq!(vec.len(), 0))
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn nameable_iterators() {
+    use demon_core::vec::{Drain, ExtractIf, IntoIter, Splice};
+
+    let mut vec: Vector<u8> = (0..10).collect();
+
+    let drain: Drain<'_, u8> = vec.drain(..2);
+    assert_eq!(drain.len(), 2);
+    drop(drain);
+
+    let splice: Splice<'_, core::ops::Range<u8>> = vec.splice(..1, 20..22);
+    assert_eq!(splice.count(), 1);
+
+    let extract: ExtractIf<'_, u8, _> = vec.extract_if(.., |x| *x >= 20);
+    assert_eq!(extract.count(), 2);
+
+    let iter: IntoIter<u8> = vec.into_iter();
+    assert!(iter.eq(3..10));
+}
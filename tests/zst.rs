@@ -0,0 +1,203 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use core::cell::Cell;
+
+use demon_core::{Exact, Global, Vector};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Zero-sized element counting how often it is dropped.
+#[derive(Clone, Debug, PartialEq)]
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+fn drops() -> usize {
+    DROPS.with(|drops| drops.take())
+}
+
+#[test]
+fn unit_push_pop() {
+    let mut vec: Vector<()> = Vector::new();
+    assert_eq!(vec.capacity(), isize::MAX as usize);
+
+    for _ in 0..1000 {
+        vec.push(());
+    }
+    assert_eq!(vec.len(), 1000);
+    assert_eq!(vec.is_inline(), false);
+    assert_eq!(vec.capacity(), isize::MAX as usize);
+
+    for _ in 0..1000 {
+        assert_eq!(vec.pop(), Some(()));
+    }
+    assert_eq!(vec.pop(), None);
+    assert_eq!(vec.is_empty(), true);
+}
+
+#[test]
+fn beyond_inline_length_limit() {
+    let mut vec: Vector<(), 1> = Vector::new_in(Global);
+    vec.extend(core::iter::repeat_n((), 500));
+    assert_eq!(vec.len(), 500);
+    assert_eq!(vec.as_slice().len(), 500);
+
+    vec.insert(250, ());
+    assert_eq!(vec.remove(0), ());
+    assert_eq!(vec.len(), 500);
+}
+
+#[test]
+fn constructors() {
+    let vec: Vector<()> = Vector::from([(); 300]);
+    assert_eq!(vec.len(), 300);
+
+    let vec = Vector::<()>::from_heap(&[(); 40]);
+    assert_eq!(vec.len(), 40);
+
+    let vec: Vector<()> = Vector::with_capacity_in(1 << 40, Global);
+    assert_eq!(vec.len(), 0);
+    assert_eq!(vec.capacity(), isize::MAX as usize);
+
+    let vec: Vector<(), 23, Global, Exact> = Vector::new_heap_in(Global);
+    assert_eq!(vec.capacity(), isize::MAX as usize);
+
+    let vec: Vector<()> = (0..77).map(|_| ()).collect();
+    assert_eq!(vec.len(), 77);
+    assert_eq!(vec.clone(), vec);
+}
+
+#[test]
+fn reserve_and_shrink() {
+    let mut vec: Vector<()> = Vector::new();
+    vec.extend(core::iter::repeat_n((), 10));
+
+    assert_eq!(vec.try_reserve(1 << 50), Ok(()));
+    assert_eq!(vec.try_reserve_exact(1 << 50), Ok(()));
+    assert!(vec.try_reserve(usize::MAX).is_err());
+
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), isize::MAX as usize);
+    assert_eq!(vec.try_inline(), false);
+    assert_eq!(vec.len(), 10);
+}
+
+#[test]
+fn drop_counting() {
+    drops();
+
+    let mut vec: Vector<Counted> = Vector::new();
+    for _ in 0..200 {
+        vec.push(Counted);
+    }
+    assert_eq!(drops(), 0);
+
+    drop(vec.pop());
+    assert_eq!(drops(), 1);
+
+    drop(vec);
+    assert_eq!(drops(), 199);
+}
+
+#[test]
+fn drop_counting_clone() {
+    drops();
+
+    let vec: Vector<Counted> = Vector::from([Counted, Counted, Counted]);
+    let cl = vec.clone();
+    assert_eq!(cl.len(), 3);
+
+    drop(vec);
+    drop(cl);
+    assert_eq!(drops(), 6);
+}
+
+#[test]
+fn into_iter() {
+    let vec: Vector<()> = Vector::from([(); 50]);
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 50);
+    assert_eq!(iter.size_hint(), (50, Some(50)));
+
+    assert_eq!(iter.next(), Some(()));
+    assert_eq!(iter.next_back(), Some(()));
+    assert_eq!(iter.len(), 48);
+    assert_eq!(iter.count(), 48);
+}
+
+#[test]
+fn into_iter_drop_counting() {
+    drops();
+
+    let vec: Vector<Counted> = (0..100).map(|_| Counted).collect();
+    let mut iter = vec.into_iter();
+    for _ in 0..30 {
+        drop(iter.next());
+    }
+    drop(iter.next_back());
+    assert_eq!(drops(), 31);
+
+    drop(iter);
+    assert_eq!(drops(), 69);
+}
+
+#[test]
+fn drain() {
+    let mut vec: Vector<()> = Vector::from([(); 150]);
+    let mut drain = vec.drain(..);
+    assert_eq!(drain.len(), 150);
+    assert_eq!(drain.next(), Some(()));
+    assert_eq!(drain.next_back(), Some(()));
+    assert_eq!(drain.size_hint(), (148, Some(148)));
+    assert_eq!(drain.count(), 148);
+
+    assert_eq!(vec.is_empty(), true);
+    vec.push(());
+    assert_eq!(vec.len(), 1);
+}
+
+#[test]
+fn drain_drop_counting() {
+    drops();
+
+    let mut vec: Vector<Counted> = (0..40).map(|_| Counted).collect();
+    let mut drain = vec.drain(..);
+    drop(drain.next());
+    assert_eq!(drops(), 1);
+
+    drop(drain);
+    assert_eq!(drops(), 39);
+    assert_eq!(vec.len(), 0);
+
+    drop(vec);
+    assert_eq!(drops(), 0);
+}
+
+#[test]
+fn no_allocation() {
+    let mut vec: Vector<()> = Vector::new();
+    vec.extend(core::iter::repeat_n((), 10_000));
+
+    let ptr = vec.as_slice().as_ptr();
+    assert_eq!(ptr, core::ptr::NonNull::<()>::dangling().as_ptr());
+}
+
+#[test]
+fn drain_range() {
+    drops();
+
+    let mut vec: Vector<Counted> = (0..300).map(|_| Counted).collect();
+    let mut drain = vec.drain(100..250);
+    assert_eq!(drain.len(), 150);
+    drop(drain.next());
+    drop(drain);
+
+    assert_eq!(drops(), 150);
+    assert_eq!(vec.len(), 150);
+}